@@ -1,11 +1,12 @@
 use iced::{
     executor,
     widget::{self, container, button, text},
-    Application, Command, Element,
+    window, Application, Command, ContentFit, Element, Length,
 };
 use iced_video::{
-    viewer::{video_view, ControlEvent},
-    AdvancedPlayer, BasicPlayer, PlayerBuilder, PlayerHandler, PlayerMessage,
+    key_map::KeyMap,
+    viewer::{Control, ControlEvent, VideoView},
+    AdvancedPlayer, BasicPlayer, PlayerBuilder, PlayerHandler, PlayerMessage, StoryboardBuilder,
 };
 
 fn main() {
@@ -23,8 +24,8 @@ enum Message {
 
 struct App {
     player_handler: PlayerHandler,
-    seek: Option<u64>,
     id: String,
+    fullscreen: bool,
 }
 
 impl Application for App {
@@ -41,19 +42,24 @@ impl Application for App {
         let url =
             "http://commondatastorage.googleapis.com/gtv-videos-bucket/sample/BigBuckBunny.mp4";
         player_handler.start_player(PlayerBuilder::new(url).set_auto_start(true).set_uri(url));
+        player_handler.start_storyboard(StoryboardBuilder::new(url, url));
 
         (
             App {
                 player_handler,
-                seek: None,
                 id: url.to_string(),
+                fullscreen: false,
             },
             Command::none(),
         )
     }
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
-        self.player_handler.subscriptions().map(Message::Video)
+        iced::Subscription::batch(vec![
+            self.player_handler.subscriptions(),
+            self.player_handler.storyboard_subscriptions(),
+        ])
+        .map(Message::Video)
     }
 
     fn title(&self) -> String {
@@ -65,6 +71,17 @@ impl Application for App {
             Message::Video(event) => {
                 self.player_handler.handle_event(event);
             }
+            Message::ControlEvent(ControlEvent::ToggleFullscreen | ControlEvent::DoubleClick) => {
+                self.fullscreen = !self.fullscreen;
+                return window::change_mode(
+                    window::Id::MAIN,
+                    if self.fullscreen {
+                        window::Mode::Fullscreen
+                    } else {
+                        window::Mode::Windowed
+                    },
+                );
+            }
             Message::ControlEvent(event) => {
                 if let Some(player) = self.player_handler.get_player_mut(&self.id) {
                     match event {
@@ -80,15 +97,26 @@ impl Application for App {
                         ControlEvent::Volume(volume) => {
                             // player.set_volume(volume)
                         }
-                        ControlEvent::Seek(p) => {
-                            self.seek = Some(p as u64);
-                        }
-                        ControlEvent::Released => {
-                            player
-                                .seek(std::time::Duration::from_secs(self.seek.unwrap()))
-                                .unwrap_or_else(|err| println!("Error seeking: {:?}", err));
-                            self.seek = None;
+                        ControlEvent::SeekTo(position) => player
+                            .seek(position)
+                            .unwrap_or_else(|err| println!("Error seeking: {:?}", err)),
+                        ControlEvent::Click => {
+                            if player.is_playing() {
+                                player.pause()
+                            } else {
+                                player.play()
+                            }
                         }
+                        ControlEvent::Speed(speed) => player
+                            .set_playback_rate(speed)
+                            .unwrap_or_else(|err| println!("Error setting speed: {:?}", err)),
+                        ControlEvent::ToggleLoop => player.set_looping(!player.get_looping()),
+                        ControlEvent::DoubleClick
+                        | ControlEvent::Drag(_)
+                        | ControlEvent::DragReleased
+                        | ControlEvent::ToggleFullscreen
+                        | ControlEvent::Track(_)
+                        | ControlEvent::Chapter(_) => {}
                     }
                 }
             }
@@ -110,6 +138,7 @@ impl Application for App {
             self.player_handler.get_player(&self.id)
         {
             let frame = self.player_handler.get_frame(&self.id);
+            let storyboard = self.player_handler.get_storyboard(&self.id);
             // if let Some(handle) = frame {
             //     let i_width = 1280 as u16;
             //     let i_height = (i_width as f32 * 9.0 / 16.0) as u16;
@@ -121,7 +150,23 @@ impl Application for App {
             //     iced::widget::image(iced::widget::image::Handle::from_pixels(0, 0, vec![])).into()
             // }
             widget::column![widget::row![text(player.get_looping()) ,button("Loop").on_press(Message::ToggleLoop(self.id.clone()))],
-            video_view(player, frame, &Message::ControlEvent, &self.seek)].into()
+            VideoView::new(player, frame, Message::ControlEvent)
+                .storyboard(storyboard)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .content_fit(ContentFit::Contain)
+                .fullscreen(self.fullscreen)
+                .key_map(KeyMap::default())
+                .controls([
+                    Control::PlayPause,
+                    Control::Time,
+                    Control::Space,
+                    Control::Speed,
+                    Control::Loop,
+                    Control::Mute,
+                    Control::Volume,
+                    Control::Fullscreen,
+                ])].into()
         } else {
             widget::Text::new("No player").size(30).into()
         };
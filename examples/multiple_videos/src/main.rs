@@ -1,11 +1,11 @@
 use iced::{
     executor,
     widget::{self, button, container, scrollable},
-    Application, Command,
+    Application, Command, Size,
 };
 use iced_video::{
-     viewer::ControlEvent, PlayerBuilder, PlayerHandler, PlayerMessage,
-     AdvancedPlayer, BasicPlayer
+     video_shader::VideoShader, viewer::ControlEvent, PixelFormat, PlayerBuilder, PlayerHandler,
+     PlayerMessage, AdvancedPlayer, BasicPlayer
 };
 
 fn main() {
@@ -22,7 +22,6 @@ enum Message {
 
 struct App {
     player_handler: PlayerHandler,
-    seek: Option<u64>,
 }
 
 impl Application for App {
@@ -43,13 +42,20 @@ impl Application for App {
             "http://commondatastorage.googleapis.com/gtv-videos-bucket/sample/ForBiggerEscapes.mp4",
         ];
 
-        urls.into_iter()
-            .for_each(|uri| player_handler.start_player(PlayerBuilder::new(uri).set_uri(uri)));
+        // the frames are only shown at 480x480, there is no need to send them any larger.
+        // they are sent as yuv and converted to rgba on the gpu by the VideoShader widget
+        urls.into_iter().for_each(|uri| {
+            player_handler.start_player(
+                PlayerBuilder::new(uri)
+                    .set_uri(uri)
+                    .set_frame_size(Size::new(480, 480))
+                    .set_pixel_format(PixelFormat::I420),
+            )
+        });
 
         (
             App {
                 player_handler,
-                seek: None,
             },
             Command::none(),
         )
@@ -84,15 +90,26 @@ impl Application for App {
                         ControlEvent::Volume(volume) => {
                             // player.set_volume(volume)
                         }
-                        ControlEvent::Seek(p) => {
-                            self.seek = Some(p as u64);
-                        }
-                        ControlEvent::Released => {
-                            player
-                                .seek(std::time::Duration::from_secs(self.seek.unwrap()))
-                                .unwrap_or_else(|err| println!("Error seeking: {:?}", err));
-                            self.seek = None;
+                        ControlEvent::SeekTo(position) => player
+                            .seek(position)
+                            .unwrap_or_else(|err| println!("Error seeking: {:?}", err)),
+                        ControlEvent::Click => {
+                            if player.is_playing() {
+                                player.pause()
+                            } else {
+                                player.play()
+                            }
                         }
+                        ControlEvent::Speed(speed) => player
+                            .set_playback_rate(speed)
+                            .unwrap_or_else(|err| println!("Error setting speed: {:?}", err)),
+                        ControlEvent::ToggleLoop => player.set_looping(!player.get_looping()),
+                        ControlEvent::DoubleClick
+                        | ControlEvent::Drag(_)
+                        | ControlEvent::DragReleased
+                        | ControlEvent::ToggleFullscreen
+                        | ControlEvent::Track(_)
+                        | ControlEvent::Chapter(_) => {}
                     }
                 }
             }
@@ -103,16 +120,17 @@ impl Application for App {
     fn view(&self) -> iced::Element<Message> {
         let players = self
             .player_handler
-            .players_and_images()
+            .get_all_players()
             .iter()
-            .map(|(id, player, handle)| {
-                let image = button(
-                    iced::widget::image((*handle).clone())
+            .filter_map(|(id, player)| {
+                let frame = self.player_handler.get_decoded_frame(id)?;
+                let video = button(
+                    VideoShader::new(id, Some(frame.clone()))
                         .height(480)
                         .width(480),
                 )
                 .on_press(Message::ControlEvent(
-                    (*id).clone(),
+                    id.clone(),
                     if player.is_playing() {
                         ControlEvent::Pause
                     } else {
@@ -120,7 +138,7 @@ impl Application for App {
                     },
                 ));
 
-                container(image).into()
+                Some(container(video).into())
             })
             .collect::<Vec<iced::Element<Message>>>();
         container(scrollable(widget::Column::with_children(players)))
@@ -1,9 +1,9 @@
 use std::ops::Deref;
 
-use playbin_core::image;
+use playbin_core::{image, Planes, Pooled};
 
-/// a wrapper around a ffmpeg frame
-pub struct Frame(pub(crate) ffmpeg::util::frame::Video);
+/// a wrapper around a rgba or yuv ffmpeg frame, taken from the frame pool of the player
+pub struct Frame(pub(crate) Pooled<ffmpeg::util::frame::Video>);
 
 impl std::fmt::Debug for Frame {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -22,17 +22,39 @@ impl Deref for Frame {
     }
 }
 
+impl Frame {
+    /// the length of a row of pixels without padding
+    fn row_len(&self) -> usize {
+        self.width() as usize * 4
+    }
+}
+
+impl AsRef<[u8]> for Frame {
+    /// the rgba pixels, the rows are only tightly packed when the stride is the row length
+    fn as_ref(&self) -> &[u8] {
+        &self.data(0)[..self.row_len() * self.height() as usize]
+    }
+}
+
 impl playbin_core::IcedImage for Frame {
     fn get_image(self) -> image::Handle {
-        let mut buffer = vec![0; (self.height() * self.width() * 4) as usize];
-        let ffmpeg_line_iter = self.data(0).chunks_exact(self.stride(0));
-
-        let slint_pixel_line_iter = buffer.chunks_exact_mut(self.stride(0));
+        let (width, height) = (self.width(), self.height());
+        if self.stride(0) == self.row_len() {
+            // shown without copying, the frame goes back to the pool once the image is dropped
+            return image::Handle::from_pixels(width, height, self);
+        }
 
-        for (source_line, dest_line) in ffmpeg_line_iter.zip(slint_pixel_line_iter) {
-            dest_line.copy_from_slice(&source_line[..])
+        // the rows are padded, copy them out tightly packed
+        let mut buffer = Vec::with_capacity(self.row_len() * height as usize);
+        for line in self.data(0).chunks(self.stride(0)).take(height as usize) {
+            buffer.extend_from_slice(&line[..self.row_len()]);
         }
+        image::Handle::from_pixels(width, height, buffer)
+    }
+}
 
-        image::Handle::from_pixels(self.width() as u32, self.height() as u32, buffer)
+impl Planes for Frame {
+    fn plane(&self, index: usize) -> Option<(&[u8], usize)> {
+        (index < self.planes()).then(|| (self.data(index), self.stride(index)))
     }
 }
@@ -4,7 +4,10 @@ use std::{path::PathBuf, sync::Arc};
 
 use ffmpeg::frame::Video;
 use futures::{future::OptionFuture, FutureExt};
-use playbin_core::{BasicPlayer, IcedImage, PlayerBuilder};
+use playbin_core::{
+    fit_frame_size, BasicPlayer, DecodedFrame, FramePool, IcedImage, PixelFormat, PlayerBuilder,
+    Size, YuvFrame,
+};
 use smol::lock::Mutex;
 
 mod audio;
@@ -34,6 +37,8 @@ pub struct Player {
     // playing_changed_callback: Box<dyn Fn(bool)>,
     event_sender: smol::channel::Sender<PlayerMessage<Self>>,
     player_builder: PlayerBuilder,
+    /// the size the frames are scaled down to fit in
+    frame_size: Arc<std::sync::Mutex<Option<Size<u32>>>>,
 }
 
 impl Player {
@@ -52,6 +57,7 @@ impl Player {
                 playing,
                 // playing_changed_callback: Box::new(playing_changed_callback),
                 event_sender,
+                frame_size: Arc::new(std::sync::Mutex::new(player_builder.frame_size)),
                 player_builder,
             },
             event_receiver,
@@ -61,17 +67,31 @@ impl Player {
     fn new_source(&mut self, path: PathBuf) -> Result<(), anyhow::Error> {
         let (control_sender, control_receiver) = smol::channel::unbounded();
 
+        #[cfg(not(feature = "iced"))]
         let event_sender = self.event_sender.clone();
 
         self.control_sender = Some(control_sender);
 
         let id = self.player_builder.id.clone();
+        #[cfg(feature = "iced")]
+        let frames = self.player_builder.frames.clone();
+        let frame_size = self.frame_size.clone();
+        let pixel_format = self.player_builder.pixel_format;
+        let output_pixel = match pixel_format {
+            PixelFormat::Rgba => ffmpeg::format::Pixel::RGBA,
+            PixelFormat::I420 => ffmpeg::format::Pixel::YUV420P,
+            PixelFormat::Nv12 => ffmpeg::format::Pixel::NV12,
+        };
+        let end_sender = self.event_sender.clone();
+        let end_id = id.clone();
 
         self.demuxer_thread = Some(Arc::new(std::thread::Builder::new()
             .name("demuxer thread".into())
             .spawn(move || {
                 smol::block_on(async move {
-                    let mut to_rgba_rescaler: Option<Rescaler> = None;
+                    let mut output_rescaler: Option<Rescaler> = None;
+                    // the converted frames are reused once the images showing them are dropped
+                    let frame_pool = FramePool::<Video>::default();
                     let mut input_context = ffmpeg::format::input(&path).unwrap();
 
                     let video_stream = input_context
@@ -82,30 +102,61 @@ impl Player {
                     let video_stream_index = video_stream.index();
                     let video_playback_thread = video::VideoPlaybackThread::start(
                         &video_stream,
-                        Box::new(move |frame| {
+                        Box::new(move |frame: &Video| -> Result<(), anyhow::Error> {
+                            let target = frame_size.lock().ok().and_then(|size| *size);
+                            let output = fit_frame_size(frame.width(), frame.height(), target);
                             let rebuild_rescaler =
-                                to_rgba_rescaler.as_ref().map_or(true, |existing_rescaler| {
+                                output_rescaler.as_ref().map_or(true, |existing_rescaler| {
                                     existing_rescaler.input().format != frame.format()
+                                        || existing_rescaler.input().width != frame.width()
+                                        || existing_rescaler.input().height != frame.height()
+                                        || existing_rescaler.output().width != output.width
+                                        || existing_rescaler.output().height != output.height
+                                        || existing_rescaler.output().format != output_pixel
                                 });
 
                             if rebuild_rescaler {
-                                to_rgba_rescaler = Some(rgba_rescaler_for_frame(frame));
+                                output_rescaler = Some(rescaler_for_frame(frame, output, output_pixel)?);
                             }
 
-                            let rescaler = to_rgba_rescaler.as_mut().unwrap();
-
-                            let mut rgb_frame = ffmpeg::util::frame::Video::empty();
-                            rescaler.run(&frame, &mut rgb_frame).unwrap();
+                            let rescaler = output_rescaler.as_mut().unwrap();
+
+                            let mut converted = frame_pool
+                                .take_matching(|pooled| {
+                                    pooled.width() == output.width
+                                        && pooled.height() == output.height
+                                        && pooled.format() == output_pixel
+                                })
+                                .unwrap_or_else(Video::empty);
+                            // a frame that can not be converted is skipped, the video keeps playing
+                            rescaler.run(frame, &mut converted)?;
+
+                            let converted = crate::Frame(frame_pool.wrap(converted));
+                            let frame = match pixel_format {
+                                PixelFormat::Rgba => {
+                                    #[cfg(feature = "iced")]
+                                    let converted = converted.get_image();
+                                    DecodedFrame::Rgba(converted)
+                                }
+                                // the planes are shown as they are, the frame goes back to the pool once dropped
+                                _ => DecodedFrame::Yuv(YuvFrame::new(
+                                    pixel_format,
+                                    output.width,
+                                    output.height,
+                                    converted,
+                                )),
+                            };
 
                             #[cfg(feature = "iced")]
-                            let frame = crate::Frame(rgb_frame).get_image();
-                            
-                            #[cfg(not(feature = "iced"))]
-                            let frame = crate::Frame(rgb_frame);
+                            frames.send(frame);
 
-                            if let Err(e) = event_sender.try_send(PlayerMessage::Frame(id.clone(),frame)) {
-                                println!("Error sending frame: {:?}", e);
+                            #[cfg(not(feature = "iced"))]
+                            {
+                                if let Err(e) = event_sender.try_send(PlayerMessage::Frame(id.clone(),frame)) {
+                                    tracing::error!("unable to send frame: {:?}", e);
+                                }
                             }
+                            Ok(())
                         }),
                     )
                     .unwrap();
@@ -151,7 +202,11 @@ impl Player {
                         smol::pin!(packet_forwarder);
 
                         futures::select! {
-                            _ = packet_forwarder => {}, // playback finished
+                            _ = packet_forwarder => {
+                                // playback finished
+                                playing = false;
+                                let _ = end_sender.try_send(PlayerMessage::EndOfStream(end_id.clone()));
+                            },
                             received_command = control_receiver.recv().fuse() => {
                                 match received_command {
                                     Ok(command) => {
@@ -190,19 +245,22 @@ struct Rescaler(ffmpeg::software::scaling::Context);
 #[allow(unsafe_code)]
 unsafe impl std::marker::Send for Rescaler {}
 
-fn rgba_rescaler_for_frame(frame: &ffmpeg::util::frame::Video) -> Rescaler {
-    Rescaler(
-        ffmpeg::software::scaling::Context::get(
-            frame.format(),
-            frame.width(),
-            frame.height(),
-            ffmpeg::format::Pixel::RGBA,
-            frame.width(),
-            frame.height(),
-            ffmpeg::software::scaling::Flags::BILINEAR,
-        )
-        .unwrap(),
+/// a scaler converting frames to `pixel` at the `output` size
+fn rescaler_for_frame(
+    frame: &ffmpeg::util::frame::Video,
+    output: Size<u32>,
+    pixel: ffmpeg::format::Pixel,
+) -> Result<Rescaler, ffmpeg::Error> {
+    ffmpeg::software::scaling::Context::get(
+        frame.format(),
+        frame.width(),
+        frame.height(),
+        pixel,
+        output.width,
+        output.height,
+        ffmpeg::software::scaling::Flags::BILINEAR,
     )
+    .map(Rescaler)
 }
 
 impl Drop for Player {
@@ -234,6 +292,13 @@ impl BasicPlayer for Player {
         self.new_source(uri.to_owned())
     }
 
+    fn set_frame_size(&self, size: Option<Size<u32>>) {
+        // the rescaler is rebuilt for the next frame
+        if let Ok(mut frame_size) = self.frame_size.lock() {
+            *frame_size = size;
+        }
+    }
+
     fn play(&self) {
         if let Some(control_sender) = self.control_sender.as_ref() {
             control_sender.try_send(ControlCommand::Play).unwrap();
@@ -14,7 +14,9 @@ pub struct VideoPlaybackThread {
 impl VideoPlaybackThread {
     pub fn start(
         stream: &ffmpeg::format::stream::Stream<'_>,
-        mut video_frame_callback: Box<dyn FnMut(&ffmpeg::util::frame::Video) + Send>,
+        mut video_frame_callback: Box<
+            dyn FnMut(&ffmpeg::util::frame::Video) -> Result<(), anyhow::Error> + Send,
+        >,
     ) -> Result<Self, anyhow::Error> {
         let (control_sender, control_receiver) = smol::channel::unbounded();
 
@@ -45,7 +47,9 @@ impl VideoPlaybackThread {
                                     let _ = smol::Timer::after(delay).await;
                                 }
 
-                                video_frame_callback(&decoded_frame);
+                                if let Err(err) = video_frame_callback(&decoded_frame) {
+                                    tracing::error!("unable to show frame: {:?}", err);
+                                }
                             }
                         }
                     }
@@ -0,0 +1,98 @@
+//! turning the samples pulled from an appsink into images
+
+use playbin_core::{image, DecodedFrame, PixelFormat, Planes, YuvFrame};
+
+use crate::GstreamerError;
+
+/// the image showing an rgba sample.
+/// the buffer of the sample stays mapped behind the image instead of copying its pixels,
+/// it is given back to gstreamer once the last clone of the image is dropped
+pub(crate) fn sample_image(sample: &gst::Sample) -> Result<image::Handle, GstreamerError> {
+    let caps = sample
+        .caps()
+        .ok_or(GstreamerError::MissingElement("caps"))?;
+    let s = caps
+        .structure(0)
+        .ok_or(GstreamerError::MissingElement("caps"))?;
+    let width = s.get::<i32>("width")? as u32;
+    let height = s.get::<i32>("height")? as u32;
+
+    let buffer = sample
+        .buffer_owned()
+        .ok_or(GstreamerError::MissingElement("buffer"))?;
+    let map = buffer
+        .into_mapped_buffer_readable()
+        .map_err(|_| GstreamerError::CustomError("unable to map buffer"))?;
+    if map.len() < (width * height * 4) as usize {
+        return Err(GstreamerError::CustomError(
+            "buffer is smaller than the frame",
+        ));
+    }
+
+    Ok(image::Handle::from_pixels(width, height, map))
+}
+
+/// the frame of a sample in the format the appsink was asked for
+pub(crate) fn sample_frame(
+    sample: &gst::Sample,
+    pixel_format: PixelFormat,
+) -> Result<DecodedFrame, GstreamerError> {
+    if pixel_format == PixelFormat::Rgba {
+        return sample_image(sample).map(DecodedFrame::Rgba);
+    }
+
+    let caps = sample
+        .caps()
+        .ok_or(GstreamerError::MissingElement("caps"))?;
+    let info = gst_video::VideoInfo::from_caps(caps)?;
+    if info.n_planes() as usize != pixel_format.planes() {
+        return Err(GstreamerError::CustomError(
+            "sample does not have the planes of its format",
+        ));
+    }
+
+    let buffer = sample
+        .buffer_owned()
+        .ok_or(GstreamerError::MissingElement("buffer"))?;
+    let map = buffer
+        .into_mapped_buffer_readable()
+        .map_err(|_| GstreamerError::CustomError("unable to map buffer"))?;
+
+    // a plane runs until the next one starts, the last one until the end of the buffer
+    let offsets = info.offset();
+    let planes = offsets
+        .iter()
+        .zip(info.stride())
+        .enumerate()
+        .map(|(index, (&offset, &stride))| {
+            let end = offsets.get(index + 1).copied().unwrap_or(map.len());
+            (offset, end.max(offset), stride.max(0) as usize)
+        })
+        .collect::<Vec<_>>();
+    if planes.iter().any(|&(_, end, _)| end > map.len()) {
+        return Err(GstreamerError::CustomError(
+            "buffer is smaller than the frame",
+        ));
+    }
+
+    Ok(DecodedFrame::Yuv(YuvFrame::new(
+        pixel_format,
+        info.width(),
+        info.height(),
+        MappedPlanes { map, planes },
+    )))
+}
+
+/// the planes of a mapped buffer, kept mapped until the frame is dropped
+struct MappedPlanes {
+    map: gst::MappedBuffer<gst::buffer::Readable>,
+    /// the start, end and stride of every plane
+    planes: Vec<(usize, usize, usize)>,
+}
+
+impl Planes for MappedPlanes {
+    fn plane(&self, index: usize) -> Option<(&[u8], usize)> {
+        let &(start, end, stride) = self.planes.get(index)?;
+        Some((self.map.get(start..end)?, stride))
+    }
+}
@@ -22,7 +22,10 @@
 
 mod error;
 mod extra_functions;
+mod frame;
 mod player;
+mod probe;
+mod storyboard;
 mod tag_convert;
 mod unsafe_functions;
 
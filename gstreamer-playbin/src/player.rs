@@ -1,11 +1,16 @@
 use std::{
     path::PathBuf,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 
 pub use crate::error::GstreamerError;
-use crate::{extra_functions::send_seek_event, unsafe_functions::is_initialized};
+use crate::{
+    extra_functions::send_seek_event, frame::sample_frame, unsafe_functions::is_initialized,
+};
 use gst::{
     glib::{Cast, ObjectExt},
     prelude::{ElementExtManual, GstBinExtManual},
@@ -13,7 +18,8 @@ use gst::{
     BusSyncReply, FlowError, FlowSuccess,
 };
 use playbin_core::{
-    image, smol::lock::Mutex, AdvancedPlayer, BasicPlayer, PlayerBuilder, PlayerMessage,
+    fit_frame_size, smol::lock::Mutex, AdvancedPlayer, BasicPlayer, PixelFormat, PlayerBuilder,
+    PlayerMessage, Size,
 };
 use tracing::{debug, error, info};
 
@@ -29,6 +35,13 @@ pub struct Player {
     video_details: Option<VideoDetails>,
     playback_rate: Arc<Mutex<f64>>,
     loop_track: Arc<AtomicBool>,
+    /// the uri switched to on `about-to-finish` for gapless playback
+    next_source: Arc<std::sync::Mutex<Option<PathBuf>>>,
+    /// the uri queued on `about-to-finish`, its stream start is sent as [`PlayerMessage::NextSource`]
+    switching: Arc<std::sync::Mutex<Option<String>>>,
+    app_sink: gst_app::AppSink,
+    /// the size the frames are scaled down to fit in
+    frame_size: Arc<std::sync::Mutex<Option<Size<u32>>>>,
 }
 
 /// stores some details about the video.
@@ -62,37 +75,24 @@ impl Player {
         let sender1 = sender.clone();
         let sender2 = sender.clone();
         let id = settings.id.clone();
-        let id1 = settings.id.clone();
+        let frames = settings.frames.clone();
+        let pixel_format = settings.pixel_format;
         let id2 = settings.id.clone();
         let _id3 = settings.id.clone();
         let loop_track = Arc::new(AtomicBool::new(false));
         let loop_track_clone = loop_track.clone();
+        // set when the next source was queued, its stream start is the switch to it
+        let switching = Arc::new(std::sync::Mutex::new(None::<String>));
+        let switching_clone = switching.clone();
 
-        let player = Self::build_player(
+        let mut player = Self::build_player(
             settings,
             move |sink: &gst_app::AppSink| {
                 let sample = sink.pull_sample().map_err(|_| FlowError::Eos)?;
-                let buffer = sample.buffer().ok_or(FlowError::Error)?;
-                let map = buffer.map_readable().map_err(|_| FlowError::Error)?;
-
-                let pad = sink.static_pad("sink").ok_or(FlowError::Error)?;
-
-                let caps = pad.current_caps().ok_or(FlowError::Error)?;
-                let s = caps.structure(0).ok_or(FlowError::Error)?;
-                let width = s.get::<i32>("width").map_err(|_| FlowError::Error)?;
-                let height = s.get::<i32>("height").map_err(|_| FlowError::Error)?;
-
-                let res = sender.try_send(PlayerMessage::Frame(
-                    id1.clone(),
-                    image::Handle::from_pixels(
-                        width as u32,
-                        height as u32,
-                        map.as_slice().to_owned(),
-                    ),
-                ));
-
-                if res.is_err() {
-                    return Err(FlowError::Error);
+                // a frame that can not be shown is skipped, the pipeline keeps playing
+                match sample_frame(&sample, pixel_format) {
+                    Ok(frame) => frames.send(frame),
+                    Err(err) => error!("unable to read frame: {:?}", err),
                 }
 
                 Ok(FlowSuccess::Ok)
@@ -115,6 +115,29 @@ impl Player {
                                 gst::ClockTime::NONE,
                             )
                             .unwrap();
+                    } else if sender2.try_send(PlayerMessage::EndOfStream(id2.clone())).is_err() {
+                        error!("Error sending end of stream");
+                    }
+                }
+
+                if let gst::MessageView::StreamStart(_) = mes {
+                    let uri = playbin
+                        .property::<Option<String>>("current-uri")
+                        .unwrap_or_default();
+                    // the stream start of any other source, e.g. one set with `set_source`, is not the switch
+                    let switched = switching_clone.lock().is_ok_and(|mut switching| {
+                        let switched = switching.as_deref() == Some(uri.as_str());
+                        if switched {
+                            *switching = None;
+                        }
+                        switched
+                    });
+                    if switched
+                        && sender2
+                            .try_send(PlayerMessage::NextSource(id2.clone(), uri))
+                            .is_err()
+                    {
+                        error!("Error sending next source");
                     }
                 }
                 // let res = sender1.send(GstreamerMessage::Message(id2.clone(), msg.clone()));
@@ -127,6 +150,31 @@ impl Player {
             loop_track_clone,
         )
         .unwrap();
+        player.switching = switching.clone();
+
+        let next_source = player.next_source.clone();
+        let loop_track = player.loop_track.clone();
+        let _ = player
+            .playbin
+            .connect("about-to-finish", false, move |args| {
+                if loop_track.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let playbin = args[0].get::<gst::Element>().ok()?;
+                let uri = next_source.lock().ok()?.take()?;
+
+                debug!("about to finish, queueing {:?}", uri);
+                // with instant-uri the rest of the current source would be cut off
+                playbin.set_property("instant-uri", false);
+                playbin.set_property("suburi", None::<String>);
+                playbin.set_property("uri", &uri);
+                playbin.set_property("instant-uri", true);
+                if let Ok(mut switching) = switching.lock() {
+                    *switching = uri.to_str().map(ToOwned::to_owned);
+                }
+                None
+            });
+
         (player, receiver)
     }
 
@@ -165,12 +213,8 @@ impl Player {
 
         app_sink.set_property("emit-signals", true);
 
-        app_sink.set_caps(Some(
-            &gst_video::VideoCapsBuilder::new()
-                .format(gst_video::VideoFormat::Rgba)
-                .pixel_aspect_ratio(gst::Fraction::new(1, 1))
-                .build(),
-        ));
+        let pixel_format = video_settings.pixel_format;
+        app_sink.set_caps(Some(&sink_caps(None, None, pixel_format)));
 
         debug!("Create the sink bin and linking");
         // Create the sink bin, add the elements and link them
@@ -204,6 +248,15 @@ impl Player {
         ghost_pad.set_active(true)?;
         bin.add_pad(&ghost_pad)?;
 
+        // the scaled size depends on the size of the video, renegotiate whenever it changes
+        let frame_size = Arc::new(std::sync::Mutex::new(video_settings.frame_size));
+        let sink = app_sink.clone();
+        let size = frame_size.clone();
+        let _ = ghost_pad.connect_notify(Some("caps"), move |pad, _| {
+            let size = size.lock().ok().and_then(|size| *size);
+            apply_frame_size(&sink, pad.current_caps().as_ref(), size, pixel_format);
+        });
+
         let mut backend = Player {
             playbin,
             bin,
@@ -212,6 +265,10 @@ impl Player {
             video_details: None,
             playback_rate: Arc::new(Mutex::new(1.0)),
             loop_track,
+            next_source: Arc::new(std::sync::Mutex::new(None)),
+            switching: Arc::new(std::sync::Mutex::new(None)),
+            app_sink,
+            frame_size,
         };
 
         if let Some(url) = backend.settings.uri.clone() {
@@ -223,6 +280,51 @@ impl Player {
     }
 }
 
+/// the caps of the appsink, frames in `pixel_format` scaled down to fit in `frame_size`.
+/// the video caps are needed to keep the aspect ratio, without them frames keep their size
+fn sink_caps(
+    video_caps: Option<&gst::Caps>,
+    frame_size: Option<Size<u32>>,
+    pixel_format: PixelFormat,
+) -> gst::Caps {
+    let format = match pixel_format {
+        PixelFormat::Rgba => gst_video::VideoFormat::Rgba,
+        PixelFormat::I420 => gst_video::VideoFormat::I420,
+        PixelFormat::Nv12 => gst_video::VideoFormat::Nv12,
+    };
+    let builder = gst_video::VideoCapsBuilder::new()
+        .format(format)
+        .pixel_aspect_ratio(gst::Fraction::new(1, 1));
+    let info = video_caps.and_then(|caps| gst_video::VideoInfo::from_caps(caps).ok());
+    let (Some(info), Some(_)) = (info, frame_size) else {
+        return builder.build();
+    };
+
+    // frames are sent with square pixels, scale the width of non square ones
+    let par = info.par();
+    let width = info.width() as u64 * par.numer().max(1) as u64 / par.denom().max(1) as u64;
+    let size = fit_frame_size(width as u32, info.height(), frame_size);
+    builder
+        .width(size.width as i32)
+        .height(size.height as i32)
+        .build()
+}
+
+/// renegotiate the caps of the appsink, `videoscale` then scales the frames to the new size
+fn apply_frame_size(
+    app_sink: &gst_app::AppSink,
+    video_caps: Option<&gst::Caps>,
+    frame_size: Option<Size<u32>>,
+    pixel_format: PixelFormat,
+) {
+    app_sink.set_caps(Some(&sink_caps(video_caps, frame_size, pixel_format)));
+    if let Some(pad) = app_sink.static_pad("sink") {
+        if !pad.push_event(gst::event::Reconfigure::new()) {
+            debug!("reconfigure event not handled");
+        }
+    }
+}
+
 impl BasicPlayer for Player {
     type Error = GstreamerError;
     fn create(
@@ -239,6 +341,16 @@ impl BasicPlayer for Player {
 
     fn set_source(&mut self, uri: &std::path::PathBuf) -> Result<(), Self::Error> {
         info!("Setting source to {:?}", uri);
+        // the queued source and subtitles belonged to the old one
+        self.set_next_source(None);
+        if let Ok(mut switching) = self.switching.lock() {
+            *switching = None;
+        }
+        self.playbin.set_property("suburi", None::<String>);
+        // setting the same uri again does nothing, reset the pipeline to play it from the start
+        if self.get_source().as_deref() == uri.to_str() {
+            let _ = self.playbin.set_state(gst::State::Ready)?;
+        }
         self.playbin.set_property("uri", &uri);
 
         self.playbin.set_property("video-sink", &self.bin);
@@ -281,6 +393,26 @@ impl BasicPlayer for Player {
         self.playbin.property("current-uri")
     }
 
+    fn set_next_source(&self, uri: Option<&PathBuf>) {
+        debug!("next source set to: {:?}", uri);
+        if let Ok(mut next_source) = self.next_source.lock() {
+            *next_source = uri.cloned();
+        }
+    }
+
+    fn set_frame_size(&self, size: Option<Size<u32>>) {
+        debug!("frame size set to: {:?}", size);
+        if let Ok(mut frame_size) = self.frame_size.lock() {
+            *frame_size = size;
+        }
+        apply_frame_size(
+            &self.app_sink,
+            self.ghost_pad.current_caps().as_ref(),
+            size,
+            self.settings.pixel_format,
+        );
+    }
+
     fn pause(&self) {
         debug!("set state to paused");
         let _ = self
@@ -343,9 +475,11 @@ impl AdvancedPlayer for Player {
 
     fn seek(&self, time: Duration) -> Result<(), Self::Error> {
         let pos = time.as_nanos() as u64;
-        debug!("seeking to: {}", time.as_secs());
-        self.playbin
-            .seek_simple(gst::SeekFlags::FLUSH, pos * gst::ClockTime::NSECOND)?;
+        debug!("seeking to: {:?}", time);
+        self.playbin.seek_simple(
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            pos * gst::ClockTime::NSECOND,
+        )?;
         Ok(())
     }
 
@@ -365,6 +499,12 @@ impl AdvancedPlayer for Player {
         )
     }
 
+    fn get_framerate(&self) -> Option<f64> {
+        self.video_details
+            .as_ref()
+            .map(|video_details| video_details.framerate)
+    }
+
     fn set_playback_rate(&self, rate: f64) -> Result<(), Self::Error> {
         debug!("set rate to: {}", rate);
         if let Some(mut playback_rate) = self.playback_rate.try_lock() {
@@ -384,6 +524,31 @@ impl AdvancedPlayer for Player {
         self.seek(Duration::ZERO)?;
         Ok(())
     }
+
+    fn set_subtitle(&self, uri: Option<&PathBuf>) -> Result<(), Self::Error> {
+        debug!("subtitle set to: {:?}", uri);
+        match uri {
+            Some(uri) => self.playbin.set_property("suburi", uri),
+            None => self.playbin.set_property("suburi", None::<String>),
+        }
+
+        // the subtitles are only picked up when the source is loaded again
+        let Some(source) = self.get_source() else {
+            return Ok(());
+        };
+        let position = self.get_position();
+        let playing = self.is_playing();
+
+        let _ = self.playbin.set_state(gst::State::Ready)?;
+        self.playbin.set_property("uri", &source);
+        let _ = self.playbin.set_state(if playing {
+            gst::State::Playing
+        } else {
+            gst::State::Paused
+        })?;
+        let _ = self.playbin.state(gst::ClockTime::from_seconds(5)).0?;
+        self.seek(position)
+    }
 }
 
 // impl PlayerBackend for GstreamerBackend {
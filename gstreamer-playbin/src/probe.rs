@@ -0,0 +1,66 @@
+//! Probing videos for their duration and a thumbnail.
+//! uses the same paused pipeline as the storyboard, one at a time on its own thread.
+
+use std::time::Duration;
+
+use gst::{prelude::ElementExtManual, traits::ElementExt};
+use playbin_core::{smol, MediaInfo, PlayerMessage, ProbeBuilder, ProbePlayer};
+use tracing::{debug, error};
+
+use crate::{
+    storyboard::{pull_thumbnail, thumbnail_pipeline},
+    GstreamerError, Player,
+};
+
+/// how far into the video the thumbnail is taken, the first frames are often black
+const THUMBNAIL_AT: f64 = 0.1;
+
+impl ProbePlayer for Player {
+    fn probe(probe_builder: ProbeBuilder) -> smol::channel::Receiver<PlayerMessage<Self>> {
+        let (sender, receiver) = smol::channel::bounded::<PlayerMessage<Self>>(1);
+
+        let res = std::thread::Builder::new()
+            .name("probe thread".into())
+            .spawn(move || {
+                let mut info = MediaInfo::default();
+                if let Err(e) = probe(&probe_builder, &mut info) {
+                    error!("error probing {:?}: {:?}", probe_builder.uri, e);
+                }
+                // whatever was found is sent, so the caller is not left waiting
+                let _ = sender.try_send(PlayerMessage::Probed(probe_builder.id, info));
+            });
+
+        if let Err(e) = res {
+            error!("unable to spawn probe thread: {}", e);
+        }
+
+        receiver
+    }
+}
+
+fn probe(settings: &ProbeBuilder, info: &mut MediaInfo) -> Result<(), GstreamerError> {
+    debug!("probing {:?}", settings.uri);
+    let (playbin, app_sink) = thumbnail_pipeline(&settings.uri, settings.width)?;
+
+    let result = (|| -> Result<(), GstreamerError> {
+        let _ = playbin.set_state(gst::State::Paused)?;
+        let _ = playbin.state(gst::ClockTime::from_seconds(5)).0?;
+
+        info.duration = playbin
+            .query_duration::<gst::ClockTime>()
+            .map(|duration| Duration::from_nanos(duration.nseconds()));
+
+        if let Some(duration) = info.duration.filter(|duration| !duration.is_zero()) {
+            playbin.seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
+                duration.mul_f64(THUMBNAIL_AT).as_nanos() as u64 * gst::ClockTime::NSECOND,
+            )?;
+            let _ = playbin.state(gst::ClockTime::from_seconds(5)).0?;
+        }
+        info.thumbnail = Some(pull_thumbnail(&app_sink)?);
+        Ok(())
+    })();
+
+    let _ = playbin.set_state(gst::State::Null)?;
+    result
+}
@@ -0,0 +1,135 @@
+//! Storyboard thumbnail generation.
+//! runs a second paused pipeline so the playing player is never interrupted.
+
+use std::{path::PathBuf, time::Duration};
+
+use gst::{
+    glib::{Cast, ObjectExt},
+    prelude::{ElementExtManual, GstBinExtManual},
+    traits::{ElementExt, PadExt},
+};
+use playbin_core::{image, smol, PlayerMessage, StoryboardBuilder, StoryboardPlayer};
+use tracing::{debug, error, info};
+
+use crate::{frame::sample_image, unsafe_functions::is_initialized, GstreamerError, Player};
+
+impl StoryboardPlayer for Player {
+    fn create_storyboard(
+        storyboard_builder: StoryboardBuilder,
+    ) -> smol::channel::Receiver<PlayerMessage<Self>> {
+        let (sender, receiver) = smol::channel::unbounded::<PlayerMessage<Self>>();
+
+        let res = std::thread::Builder::new()
+            .name("storyboard thread".into())
+            .spawn(move || {
+                if let Err(e) = generate_storyboard(&storyboard_builder, &sender) {
+                    error!("error generating storyboard: {:?}", e);
+                }
+            });
+
+        if let Err(e) = res {
+            error!("unable to spawn storyboard thread: {}", e);
+        }
+
+        receiver
+    }
+}
+
+fn generate_storyboard(
+    settings: &StoryboardBuilder,
+    sender: &smol::channel::Sender<PlayerMessage<Player>>,
+) -> Result<(), GstreamerError> {
+    info!("Generating storyboard for {:?}", settings.uri);
+
+    let (playbin, app_sink) = thumbnail_pipeline(&settings.uri, settings.width)?;
+
+    // the pipeline is set to null on errors as well, otherwise its decoder threads keep running
+    let result = (|| -> Result<(), GstreamerError> {
+        let _ = playbin.set_state(gst::State::Paused)?;
+        let _ = playbin.state(gst::ClockTime::from_seconds(5)).0?;
+
+        let duration = playbin
+            .query_duration::<gst::ClockTime>()
+            .map_or(Duration::ZERO, |d| Duration::from_nanos(d.nseconds()));
+
+        let interval = settings.interval.max(Duration::from_secs(1));
+        let mut time = Duration::ZERO;
+
+        while time < duration && !sender.is_closed() {
+            playbin.seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
+                time.as_nanos() as u64 * gst::ClockTime::NSECOND,
+            )?;
+            let _ = playbin.state(gst::ClockTime::from_seconds(5)).0?;
+
+            let _ = sender.try_send(PlayerMessage::Thumbnail(
+                settings.id.clone(),
+                time,
+                pull_thumbnail(&app_sink)?,
+            ));
+
+            time += interval;
+        }
+        Ok(())
+    })();
+
+    let _ = playbin.set_state(gst::State::Null)?;
+    result?;
+    debug!("storyboard finished for {:?}", settings.uri);
+
+    Ok(())
+}
+
+/// a paused playbin without audio sending rgba frames of the given width to the app sink
+pub(crate) fn thumbnail_pipeline(
+    uri: &PathBuf,
+    width: u32,
+) -> Result<(gst::Element, gst_app::AppSink), GstreamerError> {
+    if !is_initialized() {
+        debug!("Initialize GStreamer");
+        gst::init()?;
+    }
+
+    let playbin = gst::ElementFactory::make("playbin").build()?;
+    let audio_sink = gst::ElementFactory::make("fakesink").build()?;
+    let video_convert = gst::ElementFactory::make("videoconvert").build()?;
+    let scale = gst::ElementFactory::make("videoscale").build()?;
+
+    let app_sink = gst::ElementFactory::make("appsink")
+        .build()?
+        .dynamic_cast::<gst_app::AppSink>()
+        .expect("unable to cast appsink");
+
+    app_sink.set_caps(Some(
+        &gst_video::VideoCapsBuilder::new()
+            .format(gst_video::VideoFormat::Rgba)
+            .width(width as i32)
+            .pixel_aspect_ratio(gst::Fraction::new(1, 1))
+            .build(),
+    ));
+
+    let bin = gst::Bin::new();
+    bin.add_many(&[&video_convert, &scale, app_sink.as_ref()])?;
+    gst::Element::link_many(&[&video_convert, &scale, app_sink.as_ref()])?;
+
+    let pad = video_convert
+        .static_pad("sink")
+        .ok_or(GstreamerError::MissingElement("no ghost pad"))?;
+    let ghost_pad = gst::GhostPad::with_target(&pad)?;
+    ghost_pad.set_active(true)?;
+    bin.add_pad(&ghost_pad)?;
+
+    playbin.set_property("uri", uri);
+    playbin.set_property("audio-sink", &audio_sink);
+    playbin.set_property("video-sink", &bin);
+
+    Ok((playbin, app_sink))
+}
+
+/// the frame the paused pipeline is showing
+pub(crate) fn pull_thumbnail(app_sink: &gst_app::AppSink) -> Result<image::Handle, GstreamerError> {
+    let sample = app_sink
+        .pull_preroll()
+        .map_err(|_| GstreamerError::CustomError("unable to pull preroll sample"))?;
+    sample_image(&sample)
+}
@@ -0,0 +1,202 @@
+//! DecodedFrame is a frame sent by a player, either as rgba or as planar yuv
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+};
+
+use crate::image;
+
+/// the pixel formats a player can send its frames in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PixelFormat {
+    /// rgba, converted by the backend on the cpu
+    #[default]
+    Rgba,
+    /// a luma plane and two chroma planes at half the width and height
+    I420,
+    /// a luma plane and a plane of interleaved chroma at half the width and height
+    Nv12,
+}
+
+impl PixelFormat {
+    /// the number of planes of a frame in this format
+    pub fn planes(&self) -> usize {
+        match self {
+            PixelFormat::Rgba => 1,
+            PixelFormat::I420 => 3,
+            PixelFormat::Nv12 => 2,
+        }
+    }
+}
+
+/// the planes of a yuv frame, e.g. a mapped buffer of the backend
+pub trait Planes: Send + Sync + 'static {
+    /// the bytes of a plane and the length of its rows in bytes, the luma plane is first
+    fn plane(&self, index: usize) -> Option<(&[u8], usize)>;
+}
+
+/// a planar yuv frame, iced_video's `VideoShader` shows it without converting it on the cpu
+#[derive(Clone)]
+pub struct YuvFrame {
+    inner: Arc<YuvInner>,
+}
+
+struct YuvInner {
+    id: u64,
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+    planes: Box<dyn Planes>,
+    rgba: OnceLock<image::Handle>,
+}
+
+impl YuvFrame {
+    /// create a yuv frame of `width` x `height` pixels from its planes
+    pub fn new(format: PixelFormat, width: u32, height: u32, planes: impl Planes) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        debug_assert!(
+            format != PixelFormat::Rgba,
+            "a yuv frame needs a yuv format"
+        );
+        Self {
+            inner: Arc::new(YuvInner {
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                format,
+                width,
+                height,
+                planes: Box::new(planes),
+                rgba: OnceLock::new(),
+            }),
+        }
+    }
+
+    /// a number unique to the frame, used to upload it only once
+    pub fn id(&self) -> u64 {
+        self.inner.id
+    }
+
+    /// the format of the planes
+    pub fn format(&self) -> PixelFormat {
+        self.inner.format
+    }
+
+    /// the width in pixels
+    pub fn width(&self) -> u32 {
+        self.inner.width
+    }
+
+    /// the height in pixels
+    pub fn height(&self) -> u32 {
+        self.inner.height
+    }
+
+    /// the size of a plane in pixels, the chroma planes are half the size of the luma plane
+    pub fn plane_size(&self, index: usize) -> (u32, u32) {
+        match index {
+            0 => (self.width(), self.height()),
+            _ => (self.width().div_ceil(2), self.height().div_ceil(2)),
+        }
+    }
+
+    /// the bytes of a plane and the length of its rows in bytes
+    pub fn plane(&self, index: usize) -> Option<(&[u8], usize)> {
+        if index >= self.format().planes() {
+            return None;
+        }
+        self.inner.planes.plane(index)
+    }
+
+    /// whether the colours follow BT.709 instead of BT.601, guessed from the size like most players
+    pub fn is_hd(&self) -> bool {
+        self.height() >= 720
+    }
+
+    /// the frame converted to rgba on the cpu, for renderers that can not run shaders.
+    /// it is only converted once
+    pub fn rgba(&self) -> &image::Handle {
+        self.inner
+            .rgba
+            .get_or_init(|| image::Handle::from_pixels(self.width(), self.height(), self.to_rgba()))
+    }
+
+    fn to_rgba(&self) -> Vec<u8> {
+        let (width, height) = (self.width() as usize, self.height() as usize);
+        let mut pixels = vec![0; width * height * 4];
+        let (Some((luma, luma_stride)), Some((chroma, chroma_stride))) =
+            (self.plane(0), self.plane(1))
+        else {
+            return pixels;
+        };
+        let second = self.plane(2);
+
+        // limited range coefficients scaled by 2^16
+        let (rv, gu, gv, bu) = if self.is_hd() {
+            (117_489, 13_975, 34_925, 138_438)
+        } else {
+            (104_597, 25_675, 53_279, 132_201)
+        };
+
+        for (y, row) in pixels.chunks_exact_mut(width * 4).enumerate() {
+            for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+                let luma = luma.get(y * luma_stride + x).copied().unwrap_or(16) as i64;
+                let (u, v) = match (self.format(), second) {
+                    (PixelFormat::Nv12, _) => {
+                        let index = (y / 2) * chroma_stride + (x / 2) * 2;
+                        (chroma.get(index), chroma.get(index + 1))
+                    }
+                    (_, Some((v_plane, v_stride))) => (
+                        chroma.get((y / 2) * chroma_stride + x / 2),
+                        v_plane.get((y / 2) * v_stride + x / 2),
+                    ),
+                    _ => (None, None),
+                };
+                let u = u.copied().unwrap_or(128) as i64 - 128;
+                let v = v.copied().unwrap_or(128) as i64 - 128;
+                let c = (luma - 16) * 76_309;
+
+                let clamp = |value: i64| ((value + 32_768) >> 16).clamp(0, 255) as u8;
+                pixel[0] = clamp(c + rv * v);
+                pixel[1] = clamp(c - gu * u - gv * v);
+                pixel[2] = clamp(c + bu * u);
+                pixel[3] = 255;
+            }
+        }
+        pixels
+    }
+}
+
+impl fmt::Debug for YuvFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("YuvFrame")
+            .field("id", &self.id())
+            .field("format", &self.format())
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .finish()
+    }
+}
+
+/// a frame sent by a player, in the [`PixelFormat`] set with
+/// [`PlayerBuilder::set_pixel_format`](crate::PlayerBuilder::set_pixel_format)
+#[derive(Debug, Clone)]
+pub enum DecodedFrame<F = image::Handle> {
+    /// a rgba image
+    Rgba(F),
+    /// planar yuv, converted to rgba by the shader showing it
+    Yuv(YuvFrame),
+}
+
+impl DecodedFrame {
+    /// the frame as a rgba image, yuv frames are converted on the cpu
+    pub fn rgba(&self) -> &image::Handle {
+        match self {
+            DecodedFrame::Rgba(handle) => handle,
+            DecodedFrame::Yuv(frame) => frame.rgba(),
+        }
+    }
+}
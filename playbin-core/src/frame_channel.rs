@@ -0,0 +1,104 @@
+//! FrameSender delivers the frames of a player without letting them pile up when the ui
+//! can not keep up
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use smol::channel::{self, Receiver, Sender, TrySendError};
+
+/// how frames are delivered when they are produced faster than they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameDelivery {
+    /// only the newest frame waits to be shown, older frames are dropped
+    #[default]
+    Latest,
+    /// up to this many frames wait to be shown, the oldest is dropped to make room
+    Queue(usize),
+}
+
+impl FrameDelivery {
+    /// the most frames waiting to be shown
+    pub fn capacity(&self) -> usize {
+        match self {
+            FrameDelivery::Latest => 1,
+            FrameDelivery::Queue(capacity) => (*capacity).max(1),
+        }
+    }
+}
+
+/// how many frames a player sent and how many of them were dropped before being shown
+#[derive(Debug, Default)]
+pub struct FrameCounters {
+    sent: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl FrameCounters {
+    /// the frames sent by the player
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// the frames dropped because newer frames replaced them
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// sends the frames of a player, never blocking or failing.
+/// when the frames waiting to be shown reach the capacity of the [`FrameDelivery`]
+/// the oldest one is dropped
+#[derive(Debug, Clone)]
+pub struct FrameSender<F = crate::DecodedFrame> {
+    sender: Sender<F>,
+    receiver: Receiver<F>,
+    counters: Arc<FrameCounters>,
+}
+
+impl<F> FrameSender<F> {
+    /// create a new frame sender
+    pub fn new(delivery: FrameDelivery) -> Self {
+        let (sender, receiver) = channel::bounded(delivery.capacity());
+        Self {
+            sender,
+            receiver,
+            counters: Arc::default(),
+        }
+    }
+
+    /// send a frame, dropping the oldest waiting frames if there is no room
+    pub fn send(&self, mut frame: F) {
+        let _ = self.counters.sent.fetch_add(1, Ordering::Relaxed);
+        loop {
+            match self.sender.try_send(frame) {
+                Ok(()) => return,
+                Err(TrySendError::Full(returned)) => {
+                    frame = returned;
+                    if self.receiver.try_recv().is_ok() {
+                        let _ = self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                // the channel holds its own receiver, it is never closed
+                Err(TrySendError::Closed(_)) => return,
+            }
+        }
+    }
+
+    /// the receiving end, the subscription streams the frames straight from it
+    pub fn receiver(&self) -> Receiver<F> {
+        self.receiver.clone()
+    }
+
+    /// how many frames were sent and dropped
+    pub fn counters(&self) -> &FrameCounters {
+        &self.counters
+    }
+}
+
+impl<F> Default for FrameSender<F> {
+    fn default() -> Self {
+        Self::new(FrameDelivery::default())
+    }
+}
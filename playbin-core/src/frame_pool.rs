@@ -0,0 +1,141 @@
+//! FramePool reuses the buffers of video frames instead of allocating one for every frame
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, Weak},
+};
+
+/// a pool of frame buffers.
+/// a buffer is wrapped in a [`Pooled`] when it is sent, once the last image showing it is
+/// dropped it goes back to the pool and can be taken again for a later frame
+pub struct FramePool<T> {
+    free: Arc<Mutex<Vec<T>>>,
+    capacity: usize,
+}
+
+impl<T> FramePool<T> {
+    /// create a pool keeping at most `capacity` free buffers
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            free: Arc::new(Mutex::new(Vec::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// take a free buffer, [`None`] if all of them are still in use
+    pub fn take(&self) -> Option<T> {
+        self.free.lock().ok()?.pop()
+    }
+
+    /// take a free buffer matching `reusable`, the others are dropped.
+    /// used to drop buffers of the wrong size when the video size changes
+    pub fn take_matching(&self, reusable: impl Fn(&T) -> bool) -> Option<T> {
+        let mut free = self.free.lock().ok()?;
+        while let Some(buffer) = free.pop() {
+            if reusable(&buffer) {
+                return Some(buffer);
+            }
+        }
+        None
+    }
+
+    /// wrap a buffer so it returns to the pool once dropped
+    pub fn wrap(&self, buffer: T) -> Pooled<T> {
+        Pooled {
+            buffer: Some(buffer),
+            free: Arc::downgrade(&self.free),
+            capacity: self.capacity,
+        }
+    }
+
+    /// the number of free buffers
+    pub fn len(&self) -> usize {
+        self.free.lock().map_or(0, |free| free.len())
+    }
+
+    /// whether all buffers are in use
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for FramePool<T> {
+    /// a pool of 4 buffers, enough for a frame being shown, one sent and one being decoded
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl<T> fmt::Debug for FramePool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramePool")
+            .field("free", &self.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+/// a buffer taken from a [`FramePool`], it goes back to the pool when dropped.
+/// the pixels can be shown without copying them with
+/// [`image::Handle::from_pixels`](crate::image::Handle::from_pixels)
+pub struct Pooled<T> {
+    buffer: Option<T>,
+    free: Weak<Mutex<Vec<T>>>,
+    capacity: usize,
+}
+
+impl<T> Pooled<T> {
+    /// take the buffer out, it will not go back to the pool
+    pub fn into_inner(mut self) -> T {
+        self.buffer
+            .take()
+            .expect("the buffer is only taken when dropped")
+    }
+}
+
+impl<T> Deref for Pooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.buffer
+            .as_ref()
+            .expect("the buffer is only taken when dropped")
+    }
+}
+
+impl<T> DerefMut for Pooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.buffer
+            .as_mut()
+            .expect("the buffer is only taken when dropped")
+    }
+}
+
+impl<T: AsRef<[u8]>> AsRef<[u8]> for Pooled<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.deref().as_ref()
+    }
+}
+
+impl<T> Drop for Pooled<T> {
+    fn drop(&mut self) {
+        let (Some(buffer), Some(pool)) = (self.buffer.take(), self.free.upgrade()) else {
+            return;
+        };
+        let Ok(mut free) = pool.lock() else {
+            return;
+        };
+        if free.len() < self.capacity {
+            free.push(buffer);
+        }
+    }
+}
+
+impl<T> fmt::Debug for Pooled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pooled")
+            .field("pooled", &(self.free.strong_count() > 0))
+            .finish()
+    }
+}
@@ -21,15 +21,26 @@
 
 mod player_traits;
 mod player_builder;
+mod storyboard_builder;
+mod probe_builder;
 mod player_message;
 mod image_trait;
+mod frame_pool;
+mod frame_channel;
+mod decoded_frame;
 
 pub use iced::widget::image;
+pub use iced::Size;
 pub use smol;
 
 pub use player_traits::*;
 pub use player_builder::*;
+pub use storyboard_builder::*;
+pub use probe_builder::*;
 pub use player_message::*;
 
 pub use image_trait::*;
+pub use frame_pool::*;
+pub use frame_channel::*;
+pub use decoded_frame::*;
 
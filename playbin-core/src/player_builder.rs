@@ -2,9 +2,12 @@
 
 use std::path::PathBuf;
 
-use iced::futures::{self, SinkExt};
+use iced::{
+    futures::{self, stream, StreamExt},
+    Size,
+};
 
-use crate::{BasicPlayer, PlayerMessage};
+use crate::{BasicPlayer, FrameCounters, FrameDelivery, FrameSender, PixelFormat, PlayerMessage};
 
 /// setting when creating a player
 #[derive(Clone, Debug)]
@@ -15,6 +18,14 @@ pub struct PlayerBuilder {
     pub auto_start: bool,
     /// vdieo uri
     pub uri: Option<PathBuf>,
+    /// how frames are delivered when the ui can not keep up
+    pub frame_delivery: FrameDelivery,
+    /// the player sends its frames with it instead of its message channel
+    pub frames: FrameSender,
+    /// the size the frames are scaled down to fit in, [`None`] for the size of the video
+    pub frame_size: Option<Size<u32>>,
+    /// the pixel format frames are sent in
+    pub pixel_format: PixelFormat,
 }
 
 impl PlayerBuilder {
@@ -24,6 +35,10 @@ impl PlayerBuilder {
             id: id.into(),
             auto_start: false,
             uri: None,
+            frame_delivery: FrameDelivery::default(),
+            frames: FrameSender::default(),
+            frame_size: None,
+            pixel_format: PixelFormat::default(),
         }
     }
 
@@ -39,30 +54,75 @@ impl PlayerBuilder {
         self
     }
 
+    /// how frames are delivered when the ui can not keep up, defaults to [`FrameDelivery::Latest`]
+    pub fn set_frame_delivery(mut self, frame_delivery: FrameDelivery) -> Self {
+        self.frame_delivery = frame_delivery;
+        self.frames = FrameSender::new(frame_delivery);
+        self
+    }
+
+    /// scale the frames down to fit in the size before they are sent, keeping the aspect ratio.
+    /// small players, e.g. a grid of videos, then do not copy and upload full size frames
+    pub fn set_frame_size(mut self, size: Size<u32>) -> Self {
+        self.frame_size = Some(size);
+        self
+    }
+
+    /// send frames as planar yuv instead of rgba, iced_video's `VideoShader` then converts them
+    /// on the gpu instead of the backend on the cpu. defaults to [`PixelFormat::Rgba`]
+    pub fn set_pixel_format(mut self, pixel_format: PixelFormat) -> Self {
+        self.pixel_format = pixel_format;
+        self
+    }
+
+    /// how many frames the player sent and how many were dropped before being shown
+    pub fn frame_counters(&self) -> &FrameCounters {
+        self.frames.counters()
+    }
+
     /// build a player with the settings
     pub fn build<P: BasicPlayer + std::marker::Send + 'static>(
         self,
     ) -> iced::Subscription<PlayerMessage<P>> {
-        iced::subscription::channel(self.id.clone(), 100, move |mut sender| {
-            let settings = self.clone();
-            async move {
-                println!("creating player");
-                let (player, res) = P::create(settings);
-                println!("created player");
-                let _ = sender.send(PlayerMessage::Player(self.id.clone(), player)).await;
-                loop {
-                    let message = res.recv().await;
-                    match message {
-                        Ok(message) => {
-                            let _ = sender.send(message).await;
-                        }
-                        Err(e) => {
-                            tracing::error!("error in player: {}", e);
-                            futures::pending!()
-                        }
-                    }
-                }
-            }
+        let id = self.id.clone();
+        let player = stream::once(async move {
+            println!("creating player");
+            let id = self.id.clone();
+            let frames = self.frames.receiver();
+            let (player, res) = P::create(self);
+            println!("created player");
+            // frames are streamed straight from the frame sender, so it is the only queue
+            // they wait in and player messages never wait behind them
+            let frames = frames.map({
+                let id = id.clone();
+                move |frame| PlayerMessage::Frame(id.clone(), frame)
+            });
+            let res = res.chain(stream::once(async {
+                tracing::error!("error in player: the message channel was closed");
+                futures::future::pending().await
+            }));
+            stream::once(async { PlayerMessage::Player(id, player) })
+                .chain(stream::select(res, frames))
         })
+        .flatten();
+        iced::subscription::run_with_id(id, player)
     }
 }
+
+/// the size frames of a `width` x `height` video are sent at to fit in `frame_size`,
+/// keeping the aspect ratio. frames are only scaled down, the sizes are rounded to even numbers
+pub fn fit_frame_size(width: u32, height: u32, frame_size: Option<Size<u32>>) -> Size<u32> {
+    let Some(frame_size) = frame_size else {
+        return Size::new(width, height);
+    };
+    if width == 0 || height == 0 || (width <= frame_size.width && height <= frame_size.height) {
+        return Size::new(width, height);
+    }
+
+    let scale = f64::min(
+        frame_size.width as f64 / width as f64,
+        frame_size.height as f64 / height as f64,
+    );
+    let even = |size: f64| ((size / 2.0).round() as u32 * 2).max(2);
+    Size::new(even(width as f64 * scale), even(height as f64 * scale))
+}
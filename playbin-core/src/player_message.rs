@@ -5,7 +5,15 @@
 #[derive(Debug, Clone)]
 pub enum PlayerMessage<P, F = crate::image::Handle> {
     /// Player frame message.
-    Frame(String, F),
+    Frame(String, crate::DecodedFrame<F>),
     /// returns a new player
     Player(String, P),
+    /// Storyboard thumbnail message, the time the thumbnail was taken at.
+    Thumbnail(String, std::time::Duration, F),
+    /// the player reached the end of its source and is not looping
+    EndOfStream(String),
+    /// the player moved on to the source queued with `set_next_source` without stopping, with its uri
+    NextSource(String, String),
+    /// the result of probing a video
+    Probed(String, crate::MediaInfo<F>),
 }
@@ -1,6 +1,6 @@
 use std::{path::PathBuf, time::Duration};
 
-use crate::{PlayerBuilder, PlayerMessage};
+use crate::{PlayerBuilder, PlayerMessage, ProbeBuilder, Size, StoryboardBuilder};
 
 
 /// Basic player trait
@@ -23,6 +23,15 @@ pub trait BasicPlayer {
     /// Get the source of the player
     fn get_source(&self) -> Option<String>;
 
+    /// Queue the source played once the current one finishes, without a gap between them.
+    /// [`PlayerMessage::NextSource`] is sent when it starts playing, [`None`] clears the queue.
+    /// backends that can not do this ignore it and send [`PlayerMessage::EndOfStream`] instead
+    fn set_next_source(&self, _uri: Option<&PathBuf>) {}
+
+    /// Scale the frames down to fit in `size` keeping the aspect ratio, [`None`] sends them
+    /// at the size of the video. see [`PlayerBuilder::set_frame_size`]
+    fn set_frame_size(&self, _size: Option<Size<u32>>) {}
+
     /// pause the player
     fn pause(&self);
 
@@ -68,6 +77,12 @@ pub trait AdvancedPlayer: BasicPlayer {
     /// Get the duration of the player
     fn get_duration(&self) -> Duration;
 
+    /// Get the framerate of the video, if it is known.
+    /// defaults to [`None`], the frame based time formats then fall back to milliseconds
+    fn get_framerate(&self) -> Option<f64> {
+        None
+    }
+
     /// Set the playback rate of the player
     fn set_playback_rate(&self, rate: f64) -> Result<(), Self::Error>;
 
@@ -76,4 +91,32 @@ pub trait AdvancedPlayer: BasicPlayer {
 
     /// restart the stream usually done by seeking to 0
     fn restart_stream(&self) -> Result<(), Self::Error>;
+
+    /// Show the subtitles of a subtitle file over the current source, [`None`] removes them.
+    /// they are removed again when the source changes
+    fn set_subtitle(&self, uri: Option<&PathBuf>) -> Result<(), Self::Error>;
+}
+
+
+/// Storyboard player trait
+/// this trait is used to generate preview thumbnails for a video
+/// without interrupting the playing player
+pub trait StoryboardPlayer: BasicPlayer {
+    /// Start generating thumbnails in the background,
+    /// the channel closes once every thumbnail has been sent
+    fn create_storyboard(
+        storyboard_builder: StoryboardBuilder,
+    ) -> smol::channel::Receiver<PlayerMessage<Self>>
+    where
+        Self: Sized;
+}
+
+/// Probe player trait
+/// this trait is used to read the duration and a thumbnail of a video without playing it
+pub trait ProbePlayer: BasicPlayer {
+    /// Probe the video in the background, sends a single [`PlayerMessage::Probed`].
+    /// fields that could not be read are left empty
+    fn probe(probe_builder: ProbeBuilder) -> smol::channel::Receiver<PlayerMessage<Self>>
+    where
+        Self: Sized;
 }
@@ -0,0 +1,77 @@
+//! ProbeBuilder is used to read the duration and a thumbnail of a video without playing it
+
+use std::{path::PathBuf, time::Duration};
+
+use iced::futures::{self, SinkExt};
+
+use crate::{PlayerMessage, ProbePlayer};
+
+/// what was found out about a video by probing it
+#[derive(Debug, Clone)]
+pub struct MediaInfo<F = crate::image::Handle> {
+    /// the length of the video, [`None`] if it could not be read
+    pub duration: Option<Duration>,
+    /// a frame from near the start of the video
+    pub thumbnail: Option<F>,
+}
+
+impl<F> Default for MediaInfo<F> {
+    fn default() -> Self {
+        Self {
+            duration: None,
+            thumbnail: None,
+        }
+    }
+}
+
+/// settings when probing a video
+#[derive(Clone, Debug)]
+pub struct ProbeBuilder {
+    /// id the result is sent with
+    pub id: String,
+    /// video uri to probe
+    pub uri: PathBuf,
+    /// width of the thumbnail, the height keeps the video aspect ratio
+    pub width: u32,
+}
+
+impl ProbeBuilder {
+    /// create new probe settings
+    pub fn new(id: impl Into<String>, uri: impl Into<PathBuf>) -> Self {
+        Self {
+            id: id.into(),
+            uri: uri.into(),
+            width: 96,
+        }
+    }
+
+    /// width of the thumbnail
+    pub fn set_width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// build a subscription that probes the video in the background
+    pub fn build<P: ProbePlayer + std::marker::Send + 'static>(
+        self,
+    ) -> iced::Subscription<PlayerMessage<P>> {
+        iced::subscription::channel(
+            (self.id.clone(), self.uri.clone()),
+            1,
+            move |mut sender| async move {
+                let res = P::probe(self);
+                loop {
+                    match res.recv().await {
+                        Ok(message) => {
+                            let _ = sender.send(message).await;
+                        }
+                        Err(_) => {
+                            // probing finished, keep the subscription alive
+                            futures::pending!()
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
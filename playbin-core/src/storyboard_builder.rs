@@ -0,0 +1,68 @@
+//! StoryboardBuilder is used to configure the thumbnail generation for a video
+
+use std::{path::PathBuf, time::Duration};
+
+use iced::futures::{self, SinkExt};
+
+use crate::{PlayerMessage, StoryboardPlayer};
+
+/// settings when generating a storyboard of thumbnails for a video
+#[derive(Clone, Debug)]
+pub struct StoryboardBuilder {
+    /// id of the player the storyboard belongs to
+    pub id: String,
+    /// video uri to generate thumbnails from
+    pub uri: PathBuf,
+    /// time between thumbnails
+    pub interval: Duration,
+    /// width of each thumbnail, the height keeps the video aspect ratio
+    pub width: u32,
+}
+
+impl StoryboardBuilder {
+    /// create new storyboard settings
+    pub fn new(id: impl Into<String>, uri: impl Into<PathBuf>) -> Self {
+        Self {
+            id: id.into(),
+            uri: uri.into(),
+            interval: Duration::from_secs(10),
+            width: 160,
+        }
+    }
+
+    /// time between thumbnails
+    pub fn set_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// width of each thumbnail
+    pub fn set_width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// build a subscription that generates the thumbnails in the background
+    pub fn build<P: StoryboardPlayer + std::marker::Send + 'static>(
+        self,
+    ) -> iced::Subscription<PlayerMessage<P>> {
+        iced::subscription::channel(
+            (self.id.clone(), self.uri.clone()),
+            100,
+            move |mut sender| async move {
+                let res = P::create_storyboard(self);
+                loop {
+                    match res.recv().await {
+                        Ok(message) => {
+                            let _ = sender.send(message).await;
+                        }
+                        Err(_) => {
+                            // generation finished, keep the subscription alive
+                            futures::pending!()
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use clap::{Parser, ValueEnum};
+use iced_video::{AdvancedPlayer, Player};
+
+use crate::helpers::uri::to_uri;
+
+/// the player backends, only the one playerust was built with can be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    Gstreamer,
+    Ffmpeg,
+}
+
+impl Backend {
+    fn name(&self) -> &'static str {
+        match self {
+            Backend::Gstreamer => "gstreamer",
+            Backend::Ffmpeg => "ffmpeg",
+        }
+    }
+}
+
+/// A video player built with iced
+#[derive(Debug, Clone, Default, Parser)]
+#[command(version)]
+pub struct Cli {
+    /// files or urls to play, only the first one is played for now
+    pub inputs: Vec<String>,
+
+    /// start playing at this time, e.g. `90`, `1:30` or `1:02:03.5`
+    #[arg(long, short, value_parser = parse_time)]
+    pub start: Option<Duration>,
+
+    /// the volume from 0 to 100
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub volume: Option<u8>,
+
+    /// mute the audio
+    #[arg(long)]
+    pub mute: bool,
+
+    /// the playback rate, 1.0 is normal speed
+    #[arg(long, value_parser = parse_rate)]
+    pub rate: Option<f64>,
+
+    /// loop the video
+    #[arg(long = "loop")]
+    pub looping: bool,
+
+    /// start in fullscreen
+    #[arg(long, short)]
+    pub fullscreen: bool,
+
+    /// the player backend to use
+    #[arg(long, value_enum)]
+    pub backend: Option<Backend>,
+
+    /// play without a window, e.g. for scripts. exits once the video has finished
+    #[arg(long)]
+    pub headless: bool,
+}
+
+impl Cli {
+    /// check the options that clap can not
+    pub fn validate(&self) -> Result<(), String> {
+        match self.backend {
+            Some(backend) if backend.name() != iced_video::BACKEND => Err(format!(
+                "playerust was built with the {} backend, {} is not available",
+                iced_video::BACKEND,
+                backend.name()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// the uri of the first input
+    pub fn uri(&self) -> Result<Option<String>, String> {
+        if self.inputs.len() > 1 {
+            log::warn!(
+                "only the first file is played, {} are ignored",
+                self.inputs.len() - 1
+            );
+        }
+        self.inputs.first().map(|input| to_uri(input)).transpose()
+    }
+
+    /// the options that only last for this session
+    pub fn overrides(&self) -> Overrides {
+        Overrides {
+            volume: self.volume.map(|volume| volume as f64 / 100.0),
+            muted: self.mute,
+            rate: self.rate,
+            looping: self.looping,
+        }
+    }
+}
+
+/// the options of the [`Cli`] that only last for this session,
+/// they are set on the player but never saved with the settings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Overrides {
+    pub volume: Option<f64>,
+    pub muted: bool,
+    pub rate: Option<f64>,
+    pub looping: bool,
+}
+
+impl Overrides {
+    /// set the options on a new player, after the saved settings
+    pub fn apply(&self, player: &Player) {
+        if let Some(volume) = self.volume {
+            player.set_volume(volume);
+        }
+        if self.muted {
+            player.set_muted(true);
+        }
+        if let Some(rate) = self.rate {
+            if let Err(err) = player.set_playback_rate(rate) {
+                log::error!("could not set the playback rate: {:?}", err);
+            }
+        }
+        if self.looping {
+            player.set_looping(true);
+        }
+    }
+}
+
+/// what to do once playerust has started, taken from the [`Cli`]
+#[derive(Debug, Clone, Default)]
+pub struct Startup {
+    pub uri: Option<String>,
+    pub start: Option<Duration>,
+    pub fullscreen: bool,
+    pub overrides: Overrides,
+}
+
+/// parse `ss`, `mm:ss` or `hh:mm:ss`, the seconds can have a fraction
+fn parse_time(text: &str) -> Result<Duration, String> {
+    let invalid = || {
+        format!(
+            "invalid time `{}`, expected e.g. `90`, `1:30` or `1:02:03`",
+            text
+        )
+    };
+
+    let mut parts = text.rsplit(':');
+    let seconds: f64 = parts
+        .next()
+        .and_then(|seconds| seconds.parse().ok())
+        .filter(|seconds: &f64| seconds.is_finite() && *seconds >= 0.0)
+        .ok_or_else(invalid)?;
+
+    let mut total = seconds;
+    for (part, scale) in parts.zip([60.0, 3600.0]) {
+        let value: u64 = part.parse().map_err(|_| invalid())?;
+        total += value as f64 * scale;
+    }
+    if text.matches(':').count() > 2 {
+        return Err(invalid());
+    }
+
+    Ok(Duration::from_secs_f64(total))
+}
+
+/// parse a playback rate, it has to be positive
+fn parse_rate(text: &str) -> Result<f64, String> {
+    text.parse::<f64>()
+        .ok()
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .ok_or_else(|| format!("invalid rate `{}`, expected a positive number", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_time_formats() {
+        assert_eq!(parse_time("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_time("1:30"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_time("1:02:03"), Ok(Duration::from_secs(3723)));
+        assert_eq!(parse_time("1:02:03.5"), Ok(Duration::from_secs_f64(3723.5)));
+        assert_eq!(parse_time("0"), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn parse_time_rejects_invalid_times() {
+        for text in [
+            "", "abc", "-5", "1:-5", "1.5:30", "1:2:3:4", "inf", "NaN", "1::30",
+        ] {
+            assert!(parse_time(text).is_err(), "`{}` should not parse", text);
+        }
+    }
+
+    #[test]
+    fn parse_rate_has_to_be_positive() {
+        assert_eq!(parse_rate("1"), Ok(1.0));
+        assert_eq!(parse_rate("0.5"), Ok(0.5));
+        for text in ["0", "-1", "abc", "inf", "NaN", ""] {
+            assert!(parse_rate(text).is_err(), "`{}` should not parse", text);
+        }
+    }
+}
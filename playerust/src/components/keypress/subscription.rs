@@ -9,12 +9,12 @@ impl Subscription for KeyPressHandler {
         _state: &crate::state::State,
         _params: Self::Params,
     ) -> iced::Subscription<crate::update::Message> {
-        iced::event::listen_with(|event, _| {
-            if let iced::Event::Keyboard(key_event) = event {
+        // keys typed into a text input are captured and not shortcuts
+        iced::event::listen_with(|event, status| match (event, status) {
+            (iced::Event::Keyboard(key_event), iced::event::Status::Ignored) => {
                 Some(crate::update::Message::KeyBoardEvent(key_event))
-            } else {
-                None
             }
+            _ => None,
         })
     }
 }
@@ -0,0 +1,16 @@
+pub mod history;
+pub mod keybindings;
+pub mod recent;
+pub mod settings;
+
+use std::path::PathBuf;
+
+/// the directory the config files of playerust are kept in, e.g. `~/.config/playerust`
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("playerust"))
+}
+
+/// the directory the data of playerust is kept in, e.g. `~/.local/share/playerust`
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("playerust"))
+}
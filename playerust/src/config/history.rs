@@ -0,0 +1,222 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::data_dir;
+
+/// the name of the watch history file in the data dir
+const FILE_NAME: &str = "history.toml";
+
+/// how many files are remembered, the least recently watched are forgotten first
+const MAX_ENTRIES: usize = 500;
+
+/// where a file was left off
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// the uri of the file, kept to make the history readable
+    pub uri: String,
+    /// the position in seconds
+    pub position: f64,
+    /// when the position was recorded, in seconds since the unix epoch
+    pub updated: u64,
+}
+
+/// the positions files were left off at, keyed by the path, size and modification time
+/// of the file so a changed file at the same path does not resume
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default)]
+    entries: BTreeMap<String, Entry>,
+}
+
+impl History {
+    /// the path of the history file
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(FILE_NAME))
+    }
+
+    /// load the history from the data dir, starts empty if it can not be read
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        toml::from_str(&text).unwrap_or_else(|err| {
+            log::error!("could not parse {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    /// write the history to the data dir
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+
+        let result = toml::to_string(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                path.parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(&path, text))
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            log::error!("could not write {}: {}", path.display(), err);
+        }
+    }
+
+    /// remember where the file was left off, returns if the history changed.
+    /// positions within `threshold` of the start or the end are forgotten instead,
+    /// there is nothing to resume
+    pub fn record(
+        &mut self,
+        uri: &str,
+        position: Duration,
+        duration: Duration,
+        threshold: Duration,
+    ) -> bool {
+        let key = key(uri);
+        let near_end = !duration.is_zero() && duration.saturating_sub(position) < threshold;
+
+        if position < threshold || near_end {
+            return self.entries.remove(&key).is_some();
+        }
+
+        let updated = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let _ = self.entries.insert(
+            key,
+            Entry {
+                uri: uri.to_owned(),
+                position: position.as_secs_f64(),
+                updated,
+            },
+        );
+
+        while self.entries.len() > MAX_ENTRIES {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.updated)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                let _ = self.entries.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// where the file was left off, if it is remembered
+    pub fn position(&self, uri: &str) -> Option<Duration> {
+        self.entries
+            .get(&key(uri))
+            .map(|entry| Duration::from_secs_f64(entry.position.max(0.0)))
+    }
+
+    /// forget every file
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// the path of a `file://` uri
+fn local_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// the key of a uri in the history, a hash of the path, size and modification time for local files
+fn key(uri: &str) -> String {
+    let file = local_path(uri)
+        .and_then(|path| Some((path.canonicalize().ok()?, fs::metadata(&path).ok()?)));
+
+    let text = match file {
+        Some((path, metadata)) => {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |time| time.as_secs());
+            format!("{}\0{}\0{}", path.display(), metadata.len(), modified)
+        }
+        // streams only have their uri
+        None => uri.to_owned(),
+    };
+
+    format!("{:016x}", fnv1a(text.as_bytes()))
+}
+
+/// FNV-1a, used instead of the std hasher as it has to stay the same between builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a file uri for a file with a name that has to be percent encoded
+    fn encoded_file(name: &str) -> (PathBuf, String) {
+        let dir = std::env::temp_dir().join(format!("playerust history {}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, b"not a video").unwrap();
+        let uri = Url::from_file_path(&path).unwrap().to_string();
+        (path, uri)
+    }
+
+    #[test]
+    fn local_path_decodes_the_uri() {
+        let (path, uri) = encoded_file("key vidéo #1.mkv");
+        assert!(uri.contains("%20"));
+        assert_eq!(local_path(&uri), Some(path.clone()));
+        assert_eq!(local_path("https://example.com/video.mkv"), None);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn key_of_a_file_follows_its_contents() {
+        let (path, uri) = encoded_file("changed vidéo.mkv");
+        let before = key(&uri);
+        assert_ne!(before, key("https://example.com/changed%20vid%C3%A9o.mkv"));
+        assert_eq!(before, key(&uri));
+
+        fs::write(&path, b"a longer file that is not a video").unwrap();
+        assert_ne!(before, key(&uri));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn record_and_position() {
+        let (path, uri) = encoded_file("record vidéo #2.mkv");
+        let threshold = Duration::from_secs(10);
+        let duration = Duration::from_secs(600);
+        let mut history = History::default();
+
+        assert!(history.record(&uri, Duration::from_secs(120), duration, threshold));
+        assert_eq!(history.position(&uri), Some(Duration::from_secs(120)));
+
+        // near the end there is nothing to resume
+        assert!(history.record(&uri, Duration::from_secs(595), duration, threshold));
+        assert_eq!(history.position(&uri), None);
+        assert!(!history.record(&uri, Duration::from_secs(5), duration, threshold));
+        assert!(history.is_empty());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,572 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::PathBuf, str::FromStr, time::Duration};
+
+use iced::keyboard::{key::Named, Key, Modifiers};
+use iced_video::key_map::{self, Binding, KeyMap};
+use serde::Deserialize;
+
+use super::config_dir;
+
+/// the name of the keybindings file in the config dir
+const FILE_NAME: &str = "keybindings.toml";
+
+/// what a key can be bound to in playerust
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// an action handled by the video player
+    Player(key_map::Action),
+    OpenFile,
+    OpenUrl,
+    ToggleRecent,
+    ToggleHelp,
+    TogglePlaylist,
+    PlaylistNext,
+    PlaylistPrevious,
+}
+
+impl Action {
+    /// the name of the action in the keybindings file
+    pub fn name(&self) -> String {
+        use key_map::Action as Player;
+
+        match self {
+            Action::Player(Player::PlayPause) => "play_pause".into(),
+            Action::Player(Player::SeekForward(amount)) => {
+                format!("seek_forward_{}s", amount.as_secs())
+            }
+            Action::Player(Player::SeekBackward(amount)) => {
+                format!("seek_backward_{}s", amount.as_secs())
+            }
+            Action::Player(Player::VolumeUp) => "volume_up".into(),
+            Action::Player(Player::VolumeDown) => "volume_down".into(),
+            Action::Player(Player::ToggleMute) => "toggle_mute".into(),
+            Action::Player(Player::ToggleLoop) => "toggle_loop".into(),
+            Action::Player(Player::SpeedUp) => "speed_up".into(),
+            Action::Player(Player::SpeedDown) => "speed_down".into(),
+            Action::Player(Player::ResetSpeed) => "reset_speed".into(),
+            Action::Player(Player::ToggleFullscreen) => "toggle_fullscreen".into(),
+            Action::Player(Player::ExitFullscreen) => "exit_fullscreen".into(),
+            Action::OpenFile => "open_file".into(),
+            Action::OpenUrl => "open_url".into(),
+            Action::ToggleRecent => "toggle_recent".into(),
+            Action::ToggleHelp => "toggle_help".into(),
+            Action::TogglePlaylist => "toggle_playlist".into(),
+            Action::PlaylistNext => "playlist_next".into(),
+            Action::PlaylistPrevious => "playlist_previous".into(),
+        }
+    }
+
+    /// what the action does, shown in the help overlay
+    pub fn description(&self) -> String {
+        use key_map::Action as Player;
+
+        match self {
+            Action::Player(Player::PlayPause) => "Play and pause".into(),
+            Action::Player(Player::SeekForward(amount)) => {
+                format!("Seek forward {} seconds", amount.as_secs())
+            }
+            Action::Player(Player::SeekBackward(amount)) => {
+                format!("Seek backward {} seconds", amount.as_secs())
+            }
+            Action::Player(Player::VolumeUp) => "Volume up".into(),
+            Action::Player(Player::VolumeDown) => "Volume down".into(),
+            Action::Player(Player::ToggleMute) => "Mute sound on and off".into(),
+            Action::Player(Player::ToggleLoop) => "Loop the current media".into(),
+            Action::Player(Player::SpeedUp) => "Increase playing speed".into(),
+            Action::Player(Player::SpeedDown) => "Decrease playing speed".into(),
+            Action::Player(Player::ResetSpeed) => "Normal playing speed".into(),
+            Action::Player(Player::ToggleFullscreen) => "Full screen mode on and off".into(),
+            Action::Player(Player::ExitFullscreen) => "Full screen exit".into(),
+            Action::OpenFile => "Open a single file".into(),
+            Action::OpenUrl => "Open a network stream".into(),
+            Action::ToggleRecent => "Show and hide the recent files, 1 to 9 open them".into(),
+            Action::ToggleHelp => "Show and hide the keybindings".into(),
+            Action::TogglePlaylist => "Show and hide the playlist".into(),
+            Action::PlaylistNext => "Next track".into(),
+            Action::PlaylistPrevious => "Previous track".into(),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        use key_map::Action as Player;
+
+        let seconds = |amount: &str| {
+            amount
+                .strip_suffix('s')
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Duration::from_secs)
+                .ok_or(())
+        };
+
+        if let Some(amount) = name.strip_prefix("seek_forward_") {
+            return Ok(Action::Player(Player::SeekForward(seconds(amount)?)));
+        }
+        if let Some(amount) = name.strip_prefix("seek_backward_") {
+            return Ok(Action::Player(Player::SeekBackward(seconds(amount)?)));
+        }
+
+        Ok(match name {
+            "play_pause" => Action::Player(Player::PlayPause),
+            "volume_up" => Action::Player(Player::VolumeUp),
+            "volume_down" => Action::Player(Player::VolumeDown),
+            "toggle_mute" => Action::Player(Player::ToggleMute),
+            "toggle_loop" => Action::Player(Player::ToggleLoop),
+            "speed_up" => Action::Player(Player::SpeedUp),
+            "speed_down" => Action::Player(Player::SpeedDown),
+            "reset_speed" => Action::Player(Player::ResetSpeed),
+            "toggle_fullscreen" => Action::Player(Player::ToggleFullscreen),
+            "exit_fullscreen" => Action::Player(Player::ExitFullscreen),
+            "open_file" => Action::OpenFile,
+            "open_url" => Action::OpenUrl,
+            "toggle_recent" => Action::ToggleRecent,
+            "toggle_help" => Action::ToggleHelp,
+            "toggle_playlist" => Action::TogglePlaylist,
+            "playlist_next" => Action::PlaylistNext,
+            "playlist_previous" => Action::PlaylistPrevious,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// named keys that can be used in the keybindings file, the first name is used when writing
+const NAMED_KEYS: [(&str, Named); 28] = [
+    ("Space", Named::Space),
+    ("Enter", Named::Enter),
+    ("Tab", Named::Tab),
+    ("Escape", Named::Escape),
+    ("Backspace", Named::Backspace),
+    ("Delete", Named::Delete),
+    ("Insert", Named::Insert),
+    ("Home", Named::Home),
+    ("End", Named::End),
+    ("PageUp", Named::PageUp),
+    ("PageDown", Named::PageDown),
+    ("Left", Named::ArrowLeft),
+    ("Right", Named::ArrowRight),
+    ("Up", Named::ArrowUp),
+    ("Down", Named::ArrowDown),
+    ("F1", Named::F1),
+    ("F2", Named::F2),
+    ("F3", Named::F3),
+    ("F4", Named::F4),
+    ("F5", Named::F5),
+    ("F6", Named::F6),
+    ("F7", Named::F7),
+    ("F8", Named::F8),
+    ("F9", Named::F9),
+    ("F10", Named::F10),
+    ("F11", Named::F11),
+    ("F12", Named::F12),
+    ("Esc", Named::Escape),
+];
+
+/// parse a key like `Ctrl+Shift+Right` or `M`
+pub fn parse_binding(text: &str) -> Option<Binding> {
+    // a trailing `++` binds the plus key, e.g. `Ctrl++`
+    let (modifiers_text, key) = match text.strip_suffix("++") {
+        Some(modifiers_text) => (modifiers_text, "+"),
+        None if text == "+" => ("", "+"),
+        None => text.rsplit_once('+').unwrap_or(("", text)),
+    };
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    let parts = modifiers_text
+        .split('+')
+        .map(str::trim)
+        .filter(|modifier| !modifier.is_empty());
+
+    let mut modifiers = Modifiers::empty();
+    for modifier in parts {
+        modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => Modifiers::CTRL,
+            "shift" => Modifiers::SHIFT,
+            "alt" => Modifiers::ALT,
+            "super" | "logo" | "cmd" => Modifiers::LOGO,
+            _ => return None,
+        };
+    }
+
+    if let Some((_, named)) = NAMED_KEYS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+    {
+        return Some(Binding::named(*named, modifiers));
+    }
+
+    (key.chars().count() == 1).then(|| Binding::character(key, modifiers))
+}
+
+/// write a key the way it is parsed by [`parse_binding`]
+pub fn format_binding(binding: &Binding) -> String {
+    let mut text = String::new();
+    for (modifier, name) in [
+        (Modifiers::CTRL, "Ctrl+"),
+        (Modifiers::SHIFT, "Shift+"),
+        (Modifiers::ALT, "Alt+"),
+        (Modifiers::LOGO, "Super+"),
+    ] {
+        if binding.modifiers.contains(modifier) {
+            text.push_str(name);
+        }
+    }
+
+    match binding.key.as_ref() {
+        Key::Named(named) => match NAMED_KEYS.iter().find(|(_, key)| *key == named) {
+            Some((name, _)) => text.push_str(name),
+            None => text.push_str(&format!("{:?}", named)),
+        },
+        Key::Character(character) => text.push_str(&character.to_uppercase()),
+        Key::Unidentified => text.push('?'),
+    }
+    text
+}
+
+/// a problem with the keybindings file
+#[derive(Debug)]
+pub enum KeyBindingError {
+    Read(io::Error),
+    Parse(toml::de::Error),
+    UnknownAction(String),
+    InvalidKey {
+        action: String,
+        key: String,
+    },
+    Conflict {
+        key: String,
+        actions: (String, String),
+    },
+}
+
+impl fmt::Display for KeyBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyBindingError::Read(err) => write!(f, "could not read {}: {}", FILE_NAME, err),
+            KeyBindingError::Parse(err) => write!(f, "could not parse {}: {}", FILE_NAME, err),
+            KeyBindingError::UnknownAction(action) => write!(f, "unknown action `{}`", action),
+            KeyBindingError::InvalidKey { action, key } => {
+                write!(f, "invalid key `{}` for `{}`", key, action)
+            }
+            KeyBindingError::Conflict { key, actions } => write!(
+                f,
+                "`{}` is bound to both `{}` and `{}`",
+                key, actions.0, actions.1
+            ),
+        }
+    }
+}
+
+/// the keys of an action, either one key or a list
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// the layout of the keybindings file
+#[derive(Debug, Deserialize)]
+struct File {
+    #[serde(default)]
+    bindings: BTreeMap<String, Keys>,
+}
+
+/// the keys bound to each action in playerust
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    bindings: Vec<(Binding, Action)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings: Vec<(Binding, Action)> = vec![
+            (
+                Binding::character("o", Modifiers::empty()),
+                Action::OpenFile,
+            ),
+            (
+                Binding::character("n", Modifiers::CTRL),
+                Action::OpenUrl,
+            ),
+            (
+                Binding::character("r", Modifiers::CTRL),
+                Action::ToggleRecent,
+            ),
+            (
+                Binding::named(Named::F1, Modifiers::empty()),
+                Action::ToggleHelp,
+            ),
+            (
+                Binding::character("l", Modifiers::CTRL),
+                Action::TogglePlaylist,
+            ),
+            (
+                Binding::character("n", Modifiers::empty()),
+                Action::PlaylistNext,
+            ),
+            (
+                Binding::character("p", Modifiers::empty()),
+                Action::PlaylistPrevious,
+            ),
+        ];
+        bindings.extend(
+            KeyMap::default()
+                .bindings()
+                .map(|(binding, action)| (binding.clone(), Action::Player(*action))),
+        );
+        Self { bindings }
+    }
+}
+
+impl KeyBindings {
+    /// the path of the keybindings file
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join(FILE_NAME))
+    }
+
+    /// load the keybindings from the config dir, the file is created with the defaults if it is missing.
+    /// the entries that could not be used are returned with the keybindings
+    pub fn load() -> (Self, Vec<KeyBindingError>) {
+        let Some(path) = Self::path() else {
+            return (Self::default(), Vec::new());
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                let defaults = Self::default();
+                if let Err(err) = path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(&path, defaults.to_toml()))
+                {
+                    log::warn!("could not write {}: {}", path.display(), err);
+                }
+                (defaults, Vec::new())
+            }
+            Err(err) => (Self::default(), vec![KeyBindingError::Read(err)]),
+        }
+    }
+
+    /// parse a keybindings file, the actions in the file replace the default keys of those actions.
+    /// unknown actions, invalid keys and keys already bound to another action are left out and returned as errors
+    pub fn parse(text: &str) -> (Self, Vec<KeyBindingError>) {
+        let file: File = match toml::from_str(text) {
+            Ok(file) => file,
+            Err(err) => return (Self::default(), vec![KeyBindingError::Parse(err)]),
+        };
+
+        let mut errors = Vec::new();
+        let mut bindings = Self::default().bindings;
+
+        for (name, keys) in file.bindings {
+            let Ok(action) = name.parse::<Action>() else {
+                errors.push(KeyBindingError::UnknownAction(name));
+                continue;
+            };
+            // keep the keys where the defaults of the action were, so the order stays the same
+            let mut index = bindings
+                .iter()
+                .position(|(_, bound)| *bound == action)
+                .unwrap_or(bindings.len());
+            bindings.retain(|(_, bound)| *bound != action);
+            index = index.min(bindings.len());
+
+            let keys = match keys {
+                Keys::One(key) => vec![key],
+                Keys::Many(keys) => keys,
+            };
+            for key in keys {
+                match parse_binding(&key) {
+                    Some(binding) => {
+                        bindings.insert(index, (binding, action));
+                        index += 1;
+                    }
+                    None => errors.push(KeyBindingError::InvalidKey {
+                        action: name.clone(),
+                        key,
+                    }),
+                }
+            }
+        }
+
+        let mut key_bindings = Self { bindings };
+        errors.extend(key_bindings.remove_conflicts());
+
+        (key_bindings, errors)
+    }
+
+    /// drop the keys already bound to another action, the first binding of a key is kept
+    fn remove_conflicts(&mut self) -> Vec<KeyBindingError> {
+        let mut conflicts = Vec::new();
+        let mut kept: Vec<(Binding, Action)> = Vec::with_capacity(self.bindings.len());
+        for (binding, action) in self.bindings.drain(..) {
+            let bound = kept.iter().find(|(other, other_action)| {
+                *other_action != action && other.matches(&binding.key, binding.modifiers)
+            });
+            match bound {
+                Some((_, other_action)) => conflicts.push(KeyBindingError::Conflict {
+                    key: format_binding(&binding),
+                    actions: (other_action.name(), action.name()),
+                }),
+                None => kept.push((binding, action)),
+            }
+        }
+        self.bindings = kept;
+        conflicts
+    }
+
+    /// the action bound to the key press, if any
+    pub fn action(&self, key: &Key, modifiers: Modifiers) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.matches(key, modifiers))
+            .map(|(_, action)| *action)
+    }
+
+    /// every action with its keys, in the order they were first bound
+    pub fn actions(&self) -> Vec<(Action, Vec<&Binding>)> {
+        let mut actions: Vec<(Action, Vec<&Binding>)> = Vec::new();
+        for (binding, action) in &self.bindings {
+            match actions.iter_mut().find(|(bound, _)| bound == action) {
+                Some((_, keys)) => keys.push(binding),
+                None => actions.push((*action, vec![binding])),
+            }
+        }
+        actions
+    }
+
+    /// write the keybindings in the format of the keybindings file
+    pub fn to_toml(&self) -> String {
+        let mut text = String::from(
+            "# playerust keybindings\n\
+             # each action takes a key or a list of keys, e.g. `toggle_mute = [\"M\", \"Ctrl+M\"]`\n\
+             # actions left out keep their default keys, an empty list unbinds an action\n\
+             # seek actions take any number of seconds, e.g. `seek_forward_5s = \"Shift+Up\"`\n\
+             \n\
+             [bindings]\n",
+        );
+        for (action, keys) in self.actions() {
+            let keys: Vec<String> = keys
+                .into_iter()
+                .map(|binding| toml::Value::String(format_binding(binding)).to_string())
+                .collect();
+            let keys = match keys.as_slice() {
+                [key] => key.clone(),
+                keys => format!("[{}]", keys.join(", ")),
+            };
+            text.push_str(&format!("{} = {}\n", action.name(), keys));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_and_parse_round_trip() {
+        for text in [
+            "M",
+            "Ctrl+Shift+Right",
+            "Alt+F4",
+            "Space",
+            "Super+1",
+            "Ctrl++",
+        ] {
+            let binding = parse_binding(text).unwrap();
+            assert_eq!(format_binding(&binding), text);
+            assert_eq!(parse_binding(&format_binding(&binding)), Some(binding));
+        }
+    }
+
+    #[test]
+    fn parse_ignores_case_and_aliases() {
+        assert_eq!(
+            parse_binding("ctrl+shift+right"),
+            Some(Binding::named(
+                Named::ArrowRight,
+                Modifiers::CTRL | Modifiers::SHIFT
+            ))
+        );
+        assert_eq!(
+            parse_binding("Esc"),
+            Some(Binding::named(Named::Escape, Modifiers::empty()))
+        );
+        assert_eq!(
+            parse_binding("Cmd+q"),
+            Some(Binding::character("q", Modifiers::LOGO))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_keys_and_modifiers() {
+        assert_eq!(parse_binding(""), None);
+        assert_eq!(parse_binding("Ctrl+"), None);
+        assert_eq!(parse_binding("Nope"), None);
+        assert_eq!(parse_binding("Hyper+M"), None);
+        assert_eq!(parse_binding("Ctrl+Meta+M"), None);
+    }
+
+    #[test]
+    fn parse_keeps_the_valid_entries() {
+        let (key_bindings, errors) = KeyBindings::parse(
+            "[bindings]\n\
+             toggle_mute = [\"Ctrl+M\", \"Hyper+M\"]\n\
+             fly_away = \"F\"\n",
+        );
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(
+            |error| matches!(error, KeyBindingError::UnknownAction(action) if action == "fly_away")
+        ));
+        assert!(errors.iter().any(
+            |error| matches!(error, KeyBindingError::InvalidKey { key, .. } if key == "Hyper+M")
+        ));
+        assert_eq!(
+            key_bindings.action(&Key::Character("m".into()), Modifiers::CTRL),
+            Some(Action::Player(key_map::Action::ToggleMute))
+        );
+        assert_eq!(
+            key_bindings.action(&Key::Named(Named::F1), Modifiers::empty()),
+            Some(Action::ToggleHelp)
+        );
+    }
+
+    #[test]
+    fn parse_reports_and_drops_conflicts() {
+        let (key_bindings, errors) = KeyBindings::parse(
+            "[bindings]\n\
+             open_file = \"Ctrl+J\"\n\
+             open_url = [\"Ctrl+J\", \"Ctrl+U\"]\n",
+        );
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            KeyBindingError::Conflict { key, actions }
+                if key == "Ctrl+J" && actions == &("open_file".into(), "open_url".into())
+        ));
+        assert_eq!(
+            key_bindings.action(&Key::Character("j".into()), Modifiers::CTRL),
+            Some(Action::OpenFile)
+        );
+        assert_eq!(
+            key_bindings.action(&Key::Character("u".into()), Modifiers::CTRL),
+            Some(Action::OpenUrl)
+        );
+    }
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        let mut key_bindings = KeyBindings::default();
+        assert!(key_bindings.remove_conflicts().is_empty());
+        let (_, errors) = KeyBindings::parse(&key_bindings.to_toml());
+        assert!(errors.is_empty());
+    }
+}
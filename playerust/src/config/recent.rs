@@ -0,0 +1,117 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::data_dir;
+
+/// the name of the recent files file in the data dir
+const FILE_NAME: &str = "recent.toml";
+
+/// how many files are remembered, the least recently opened are forgotten first
+pub const MAX_ENTRIES: usize = 10;
+
+/// the files and playlists opened most recently, the most recent first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Recent {
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+impl Recent {
+    /// the path of the recent files file
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(FILE_NAME))
+    }
+
+    /// load the recent files from the data dir, local files that are gone are left out.
+    /// starts empty if it can not be read
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        let mut recent: Self = toml::from_str(&text).unwrap_or_else(|err| {
+            log::error!("could not parse {}: {}", path.display(), err);
+            Self::default()
+        });
+        recent.prune();
+        recent
+    }
+
+    /// write the recent files to the data dir
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+
+        let result = toml::to_string(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                path.parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(&path, text))
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            log::error!("could not write {}: {}", path.display(), err);
+        }
+    }
+
+    /// move the uri to the top of the list
+    pub fn add(&mut self, uri: &str) {
+        self.files.retain(|file| file != uri);
+        self.files.insert(0, uri.to_owned());
+        self.files.truncate(MAX_ENTRIES);
+        self.save();
+    }
+
+    /// forget the local files that no longer exist, returns whether any were forgotten
+    pub fn prune(&mut self) -> bool {
+        let len = self.files.len();
+        self.files.retain(|file| exists(file));
+        if self.files.len() == len {
+            return false;
+        }
+        self.save();
+        true
+    }
+
+    /// forget every file
+    pub fn clear(&mut self) {
+        self.files.clear();
+        self.save();
+    }
+
+    /// the uris, the most recent first
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// the local path of a `file://` uri
+pub fn local_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// whether the uri can still be opened, streams are always kept
+pub fn exists(uri: &str) -> bool {
+    match local_path(uri) {
+        Some(path) => path.exists(),
+        None => true,
+    }
+}
+
+/// the name shown for the uri, the file name of local files
+pub fn name(uri: &str) -> String {
+    local_path(uri)
+        .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| uri.to_owned())
+}
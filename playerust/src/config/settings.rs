@@ -0,0 +1,113 @@
+use std::{fmt, fs, path::PathBuf};
+
+use iced_video::{AdvancedPlayer, Player};
+use serde::{Deserialize, Serialize};
+
+use super::config_dir;
+
+/// the name of the settings file in the config dir
+const FILE_NAME: &str = "settings.toml";
+
+/// the themes playerust can be shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Light,
+    Dark,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 3] = [ThemeName::Default, ThemeName::Light, ThemeName::Dark];
+}
+
+impl fmt::Display for ThemeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ThemeName::Default => "Default",
+            ThemeName::Light => "Light",
+            ThemeName::Dark => "Dark",
+        })
+    }
+}
+
+/// the settings kept between launches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub volume: f64,
+    pub muted: bool,
+    pub playback_rate: f64,
+    pub looping: bool,
+    pub theme: ThemeName,
+    /// files are offered to resume once they are watched for this many seconds
+    pub resume_threshold: u64,
+    /// the width and height of the window
+    pub window_size: (f32, f32),
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            playback_rate: 1.0,
+            looping: false,
+            theme: ThemeName::default(),
+            resume_threshold: 30,
+            window_size: (1024.0, 768.0),
+        }
+    }
+}
+
+impl Settings {
+    /// the path of the settings file
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join(FILE_NAME))
+    }
+
+    /// load the settings from the config dir, falls back to the defaults if they can not be read
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        toml::from_str(&text).unwrap_or_else(|err| {
+            log::error!("could not parse {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    /// write the settings to the config dir
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+
+        let result = toml::to_string(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                path.parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(&path, text))
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            log::error!("could not write {}: {}", path.display(), err);
+        }
+    }
+
+    /// set up a new player with the saved volume, mute, playback rate and loop state
+    pub fn apply(&self, player: &Player) {
+        player.set_volume(self.volume);
+        player.set_muted(self.muted);
+        player.set_looping(self.looping);
+        if let Err(err) = player.set_playback_rate(self.playback_rate) {
+            log::error!("could not set the playback rate: {:?}", err);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use iced_video::{smol, AdvancedPlayer, BasicPlayer, Player, PlayerBuilder, PlayerMessage};
+
+use crate::{cli::Startup, config::settings::Settings};
+
+/// play without a window until the video has finished
+pub fn run(settings: &Settings, startup: Startup) -> Result<(), String> {
+    let uri = startup
+        .uri
+        .ok_or("nothing to play, pass a file or url to play headless")?;
+
+    let (player, receiver) = Player::create(
+        PlayerBuilder::new("main player")
+            .set_auto_start(true)
+            .set_uri(uri),
+    );
+    settings.apply(&player);
+    startup.overrides.apply(&player);
+    if let Some(start) = startup.start {
+        player
+            .seek(start)
+            .map_err(|err| format!("could not seek to the start: {:?}", err))?;
+    }
+
+    // a looping player never reaches the end, it plays until playerust is stopped
+    smol::block_on(async {
+        while let Ok(message) = receiver.recv().await {
+            if let PlayerMessage::EndOfStream(_) = message {
+                break;
+            }
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::{path::PathBuf, time::Duration};
+
+use iced::Command;
+use iced_video::{playlist::PlaylistFormat, smol::Timer, AdvancedPlayer};
+
+use crate::{
+    helpers::{
+        playlist::{enqueue, load_playlist},
+        source::set_source,
+        uri::path_to_uri,
+    },
+    state::State,
+    update::Message,
+};
+
+/// files dropped together arrive one by one, they are opened once no more came for this long
+const DROP_DELAY: Duration = Duration::from_millis(100);
+
+/// the extensions of subtitle files, they are shown over the current video
+const SUBTITLE_EXTENSIONS: [&str; 6] = ["srt", "ass", "ssa", "vtt", "sub", "smi"];
+
+/// collect a dropped file, opening the files once they have all arrived
+pub fn file_dropped(state: &mut State, path: PathBuf) -> Command<Message> {
+    state.dropped.push(path);
+    if state.dropped.len() > 1 {
+        return Command::none();
+    }
+    Command::perform(
+        async {
+            let _ = Timer::after(DROP_DELAY).await;
+        },
+        |_| Message::OpenDropped,
+    )
+}
+
+/// open the dropped files by their extension.
+/// a playlist replaces the current one, subtitles are shown over the current video,
+/// a single video is opened and several are added to the playlist
+pub fn open_dropped(state: &mut State) {
+    let mut videos = Vec::new();
+    for path in std::mem::take(&mut state.dropped) {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+
+        if PlaylistFormat::from_path(&path).is_some() {
+            load_playlist(state, &path);
+        } else if SUBTITLE_EXTENSIONS.contains(&extension.as_str()) {
+            set_subtitle(state, &path);
+        } else if let Some(uri) = path_to_uri(&path) {
+            videos.push(uri);
+        } else {
+            log::error!("could not open {}", path.display());
+        }
+    }
+
+    match videos.len() {
+        0 => {}
+        1 => set_source(state, videos.remove(0)),
+        _ => enqueue(state, videos),
+    }
+}
+
+fn set_subtitle(state: &State, path: &std::path::Path) {
+    let (Some(player), Some(uri)) = (
+        state.player_handler.get_player("main player"),
+        path_to_uri(path),
+    ) else {
+        return;
+    };
+    if let Err(err) = player.set_subtitle(Some(&PathBuf::from(uri))) {
+        log::error!("could not load the subtitles {}: {:?}", path.display(), err);
+    }
+}
@@ -0,0 +1,16 @@
+use iced::{window, Command};
+
+use crate::{state::State, update::Message};
+
+/// switch the main window in or out of fullscreen
+pub fn set_fullscreen(state: &mut State, fullscreen: bool) -> Command<Message> {
+    state.fullscreen = fullscreen;
+    window::change_mode(
+        window::Id::MAIN,
+        if fullscreen {
+            window::Mode::Fullscreen
+        } else {
+            window::Mode::Windowed
+        },
+    )
+}
@@ -1,3 +1,8 @@
 
 pub mod component_trait;
+pub mod dropped;
+pub mod fullscreen;
 pub mod open_file;
+pub mod playlist;
+pub mod source;
+pub mod uri;
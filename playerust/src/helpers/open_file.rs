@@ -1,5 +1,7 @@
 use rfd::AsyncFileDialog;
 
+use super::uri::path_to_uri;
+
 pub async fn open_file() -> Option<String> {
     let file = AsyncFileDialog::new()
         .add_filter(
@@ -16,12 +18,5 @@ pub async fn open_file() -> Option<String> {
         .set_directory("/")
         .pick_file()
         .await;
-    if let Some(file) = file {
-        Some(format!(
-            "file:///{}",
-            file.path().to_str().unwrap().to_string()
-        ))
-    } else {
-        None
-    }
+    file.and_then(|file| path_to_uri(file.path()))
 }
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use iced_video::{
+    playlist::{Playlist, PlaylistEvent, PlaylistFormat, PlaylistItem},
+    BasicPlayer, ProbeBuilder,
+};
+use rfd::AsyncFileDialog;
+
+use crate::{
+    helpers::{
+        source::{offer_resume, remember_position},
+        uri::path_to_uri,
+    },
+    state::State,
+    view::THUMBNAIL_WIDTH,
+};
+
+pub async fn open_playlist() -> Option<PathBuf> {
+    let extensions: Vec<&str> = PlaylistFormat::ALL
+        .iter()
+        .flat_map(|format| format.extensions())
+        .copied()
+        .collect();
+
+    AsyncFileDialog::new()
+        .add_filter("Playlists", &extensions)
+        .pick_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+}
+
+pub async fn save_playlist() -> Option<PathBuf> {
+    let mut dialog = AsyncFileDialog::new().set_file_name("playlist.m3u8");
+    for format in PlaylistFormat::ALL {
+        dialog = dialog.add_filter(format.name(), format.extensions());
+    }
+    dialog
+        .save_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+}
+
+/// play a playlist file from the start
+pub fn load_playlist(state: &mut State, path: &Path) {
+    let playlist = match Playlist::load(path) {
+        Ok(playlist) => playlist,
+        Err(err) => {
+            log::error!("could not open {}: {}", path.display(), err);
+            return;
+        }
+    };
+
+    if let Some(uri) = path_to_uri(path) {
+        state.recent.add(&uri);
+    }
+    play_playlist(state, playlist);
+}
+
+/// replace the playlist and play it from the start
+pub fn play_playlist(state: &mut State, playlist: Playlist) {
+    remember_position(state);
+    probe_items(state, playlist.items());
+    state
+        .player_handler
+        .attach_playlist("main player", playlist);
+    if let Some(event) = state.player_handler.next("main player") {
+        playlist_event(state, event);
+    }
+}
+
+/// add files to the end of the playlist, starting a new one if there is none
+pub fn enqueue(state: &mut State, uris: Vec<String>) {
+    let items: Vec<PlaylistItem> = uris.into_iter().map(PlaylistItem::new).collect();
+    let Some(playlist) = state.player_handler.get_playlist_mut("main player") else {
+        play_playlist(state, items.into_iter().collect());
+        return;
+    };
+
+    playlist.extend(items.iter().cloned());
+    probe_items(state, &items);
+    state.player_handler.queue_next("main player");
+}
+
+/// read the durations and thumbnails shown in the playlist
+fn probe_items(state: &mut State, items: &[PlaylistItem]) {
+    for item in items {
+        state
+            .player_handler
+            .start_probe(ProbeBuilder::new(&item.uri, &item.uri).set_width(THUMBNAIL_WIDTH.into()));
+    }
+}
+
+/// save the playlist, or the current file if there is no playlist
+pub fn write_playlist(state: &State, path: &Path) {
+    let playlist = match state.player_handler.get_playlist("main player") {
+        Some(playlist) => playlist.clone(),
+        None => state
+            .player_handler
+            .get_player("main player")
+            .and_then(|player| player.get_source())
+            .map(PlaylistItem::new)
+            .into_iter()
+            .collect(),
+    };
+
+    // without a known extension the playlist is saved as m3u8
+    let path = match PlaylistFormat::from_path(path) {
+        Some(_) => path.to_path_buf(),
+        None => path.with_extension(PlaylistFormat::M3u.extensions()[0]),
+    };
+    if let Err(err) = playlist.save(&path) {
+        log::error!("could not save {}: {}", path.display(), err);
+    }
+}
+
+/// the playlist moved on to another file
+pub fn playlist_event(state: &mut State, event: PlaylistEvent) {
+    match event {
+        PlaylistEvent::Changed { uri, .. } => offer_resume(state, &uri),
+        PlaylistEvent::Finished { .. } => {
+            log::info!("playlist finished");
+            state.resume = None;
+        }
+    }
+}
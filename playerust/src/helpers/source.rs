@@ -0,0 +1,50 @@
+use std::{path::PathBuf, time::Duration};
+
+use iced_video::{AdvancedPlayer, BasicPlayer};
+
+use crate::state::State;
+
+/// remember where the current file was left off in the watch history
+pub fn remember_position(state: &mut State) {
+    let Some(player) = state.player_handler.get_player("main player") else {
+        return;
+    };
+    let Some(uri) = player.get_source() else {
+        return;
+    };
+
+    if state.history.record(
+        &uri,
+        player.get_position(),
+        player.get_duration(),
+        Duration::from_secs(state.settings.resume_threshold),
+    ) {
+        state.history_changed();
+    }
+}
+
+/// play a new file, offering to resume it where it was left off.
+/// the playlist is replaced by the file
+pub fn set_source(state: &mut State, uri: String) {
+    remember_position(state);
+    let _ = state.player_handler.detach_playlist("main player");
+
+    let Some(player) = state.player_handler.get_player_mut("main player") else {
+        return;
+    };
+    if let Err(err) = player.set_source(&PathBuf::from(&uri)) {
+        log::error!("could not open {}: {:?}", uri, err);
+        return;
+    }
+    state.recent.add(&uri);
+    offer_resume(state, &uri);
+}
+
+/// offer to resume the file if it was left off far enough in
+pub fn offer_resume(state: &mut State, uri: &str) {
+    let threshold = Duration::from_secs(state.settings.resume_threshold);
+    state.resume = state
+        .history
+        .position(uri)
+        .filter(|position| *position >= threshold);
+}
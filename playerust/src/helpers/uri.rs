@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use url::Url;
+
+/// the url schemes the player can open
+pub const SCHEMES: [&str; 4] = ["http", "https", "rtsp", "file"];
+
+/// turn a path or url given by the user into the uri given to the player
+pub fn to_uri(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("enter a url or a path to a file".into());
+    }
+    if !input.contains("://") {
+        let path = Path::new(input)
+            .canonicalize()
+            .map_err(|err| format!("could not open `{}`: {}", input, err))?;
+        return path_to_uri(&path).ok_or_else(|| format!("could not open `{}`", path.display()));
+    }
+
+    let url = Url::parse(input).map_err(|err| format!("invalid url `{}`: {}", input, err))?;
+    match url.scheme() {
+        "file" => {
+            let path = url
+                .to_file_path()
+                .map_err(|_| format!("`{}` is not a local file", input))?;
+            if !path.exists() {
+                return Err(format!("`{}` does not exist", path.display()));
+            }
+        }
+        scheme if SCHEMES.contains(&scheme) => {
+            if url.host_str().unwrap_or_default().is_empty() {
+                return Err(format!("`{}` has no host", input));
+            }
+        }
+        scheme => {
+            return Err(format!(
+                "unsupported scheme `{}`, expected one of {}",
+                scheme,
+                SCHEMES.join(", ")
+            ))
+        }
+    }
+    Ok(url.into())
+}
+
+/// the `file://` uri of an absolute path, with the characters a uri can not hold escaped
+pub fn path_to_uri(path: &Path) -> Option<String> {
+    Url::from_file_path(path).ok().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    /// a file in the temp dir with a name that has to be escaped in a uri
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("playerust uri {}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, b"not a video").unwrap();
+        path.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn absolute_path() {
+        let path = temp_file("video.mkv");
+        let uri = to_uri(path.to_str().unwrap()).unwrap();
+
+        assert!(uri.starts_with("file:///"), "{}", uri);
+        assert_eq!(path_to_uri(&path), Some(uri.clone()));
+        assert_eq!(Url::parse(&uri).unwrap().to_file_path(), Ok(path.clone()));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn path_with_spaces_and_hash() {
+        let path = temp_file("my video #1.mkv");
+        let uri = to_uri(path.to_str().unwrap()).unwrap();
+
+        assert!(uri.ends_with("/my%20video%20%231.mkv"), "{}", uri);
+        assert_eq!(Url::parse(&uri).unwrap().to_file_path(), Ok(path.clone()));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn relative_path_is_made_absolute() {
+        let path = Path::new("Cargo.toml").canonicalize().unwrap();
+        assert_eq!(to_uri("Cargo.toml"), Ok(path_to_uri(&path).unwrap()));
+        assert!(to_uri("no such video.mkv").is_err());
+        assert!(path_to_uri(Path::new("relative/video.mkv")).is_none());
+    }
+
+    #[test]
+    fn uri_is_kept() {
+        assert_eq!(
+            to_uri(" https://example.com/my%20video.mkv ").as_deref(),
+            Ok("https://example.com/my%20video.mkv")
+        );
+        assert_eq!(
+            to_uri("rtsp://camera.local/stream").as_deref(),
+            Ok("rtsp://camera.local/stream")
+        );
+
+        let path = temp_file("already a uri #2.mkv");
+        let uri = path_to_uri(&path).unwrap();
+        assert_eq!(to_uri(&uri), Ok(uri));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn invalid_uri() {
+        assert!(to_uri("").is_err());
+        assert!(to_uri("ftp://example.com/video.mkv").is_err());
+        assert!(to_uri("https://").is_err());
+        assert!(to_uri("file:///no/such/video.mkv").is_err());
+    }
+}
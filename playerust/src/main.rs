@@ -1,3 +1,5 @@
+pub mod cli;
+pub mod headless;
 pub mod state;
 pub mod subscriptions;
 pub mod theme;
@@ -5,8 +7,12 @@ pub mod update;
 pub mod view;
 pub mod helpers;
 pub mod components;
+pub mod config;
 
-use iced::{executor, Application};
+use clap::Parser;
+use cli::{Cli, Startup};
+use config::settings::Settings;
+use iced::{executor, window, Application, Size};
 use iced_video::BasicPlayer;
 use state::State;
 use subscriptions::subscriptions;
@@ -15,7 +21,43 @@ use view::view;
 
 fn main() {
     env_logger::init();
-    State::run(Default::default()).unwrap();
+    let cli = Cli::parse();
+    if let Err(err) = cli.validate() {
+        exit(&err);
+    }
+    let uri = cli.uri().unwrap_or_else(|err| exit(&err));
+
+    let settings = Settings::load();
+    let startup = Startup {
+        uri,
+        start: cli.start,
+        fullscreen: cli.fullscreen,
+        overrides: cli.overrides(),
+    };
+
+    if cli.headless {
+        if let Err(err) = headless::run(&settings, startup) {
+            exit(&err);
+        }
+        return;
+    }
+
+    let (width, height) = settings.window_size;
+    State::run(iced::Settings {
+        window: window::Settings {
+            size: Size::new(width, height),
+            // the position in the current file is saved before closing
+            exit_on_close_request: false,
+            ..Default::default()
+        },
+        ..iced::Settings::with_flags((settings, startup))
+    })
+    .unwrap();
+}
+
+fn exit(err: &str) -> ! {
+    eprintln!("playerust: {}", err);
+    std::process::exit(1)
 }
 
 pub type Element<'a> = iced::Element<'a, Message,theme::Theme, iced::Renderer>;
@@ -27,10 +69,14 @@ impl Application for State {
 
     type Theme = theme::Theme;
 
-    type Flags = ();
+    type Flags = (Settings, Startup);
+
+    fn new((settings, startup): Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        State::new(settings, startup)
+    }
 
-    fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        State::new()
+    fn theme(&self) -> Self::Theme {
+        self.settings.theme.into()
     }
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
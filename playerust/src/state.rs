@@ -1,27 +1,150 @@
 
-use iced::Command;
+use std::{path::PathBuf, time::Duration};
+
+use iced::{time::Instant, Command};
 use iced_video::{  PlayerBuilder, PlayerHandler};
 
-use crate::update::Message;
+use crate::{
+    cli::{Overrides, Startup},
+    config::{history::History, keybindings::KeyBindings, recent::Recent, settings::Settings},
+    helpers::fullscreen::set_fullscreen,
+    update::Message,
+};
+
+/// how long the settings and the watch history have to stay unchanged before they are written to disk
+pub const SETTINGS_SAVE_DELAY: Duration = Duration::from_secs(1);
+
+/// the panels that can be shown over the video
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Help,
+    Settings,
+    OpenUrl,
+    Recent,
+}
 
 pub struct State {
     pub player_handler: PlayerHandler,
-    pub seek: Option<u64>,
     pub title: String,
+    pub fullscreen: bool,
+    pub keybindings: KeyBindings,
+    /// problems found in the keybindings file, shown in the help overlay
+    pub keybinding_errors: Vec<String>,
+    pub panel: Option<Panel>,
+    pub settings: Settings,
+    /// when the settings were last changed without being saved
+    pub settings_changed: Option<Instant>,
+    pub history: History,
+    /// when the history was last changed without being saved
+    pub history_changed: Option<Instant>,
+    pub recent: Recent,
+    /// the position the current file was left off at, offered to resume
+    pub resume: Option<Duration>,
+    /// the position to seek to once the player has started
+    pub start_at: Option<Duration>,
+    /// the options given on the command line for this session, set on the player once it has started
+    pub overrides: Overrides,
+    /// the text typed into the open url dialog
+    pub url_input: String,
+    /// why the url could not be opened, shown in the open url dialog
+    pub url_error: Option<String>,
+    pub show_playlist: bool,
+    /// the playlist item being dragged
+    pub playlist_drag: Option<usize>,
+    /// the playlist item under the cursor while dragging
+    pub playlist_hover: Option<usize>,
+    /// files dropped onto the window that are not opened yet
+    pub dropped: Vec<PathBuf>,
 }
 
 impl State {
-    pub fn new() -> (State, Command<Message>) {
+    pub fn new(settings: Settings, startup: Startup) -> (State, Command<Message>) {
         let mut player_handler = PlayerHandler::default();
 
-        player_handler.start_player(PlayerBuilder::new("main player").set_auto_start(true));
-        (
-            State {
-                player_handler,
-                seek: None,
-                title: String::from("Video Player"),
-            },
-            Command::none(),
-        )
+        let mut player = PlayerBuilder::new("main player").set_auto_start(true);
+        if let Some(uri) = &startup.uri {
+            player = player.set_uri(uri);
+        }
+        player_handler.start_player(player);
+
+        let (keybindings, errors) = KeyBindings::load();
+        let keybinding_errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        for error in &keybinding_errors {
+            log::error!("keybindings: {}", error);
+        }
+        let history = History::load();
+        let mut recent = Recent::load();
+        if let Some(uri) = &startup.uri {
+            recent.add(uri);
+        }
+        let threshold = Duration::from_secs(settings.resume_threshold);
+        let resume = match (&startup.uri, startup.start) {
+            (Some(uri), None) => history
+                .position(uri)
+                .filter(|position| *position >= threshold),
+            _ => None,
+        };
+
+        let mut state = State {
+            player_handler,
+            title: String::from("Video Player"),
+            fullscreen: false,
+            panel: (!keybinding_errors.is_empty()).then_some(Panel::Help),
+            keybindings,
+            keybinding_errors,
+            settings,
+            settings_changed: None,
+            history,
+            history_changed: None,
+            recent,
+            resume,
+            start_at: startup.start,
+            overrides: startup.overrides,
+            url_input: String::new(),
+            url_error: None,
+            show_playlist: false,
+            playlist_drag: None,
+            playlist_hover: None,
+            dropped: Vec::new(),
+        };
+        let command = if startup.fullscreen {
+            set_fullscreen(&mut state, true)
+        } else {
+            Command::none()
+        };
+        (state, command)
+    }
+
+    /// save the settings once they stopped changing, e.g. after the volume slider is let go
+    pub fn settings_changed(&mut self) {
+        self.settings_changed = Some(Instant::now());
+    }
+
+    /// write the settings to disk if they changed since they were last saved
+    pub fn save_settings(&mut self) {
+        if self.settings_changed.take().is_some() {
+            self.settings.save();
+        }
+    }
+
+    /// save the history once it stopped changing, e.g. after skipping through a playlist
+    pub fn history_changed(&mut self) {
+        self.history_changed = Some(Instant::now());
+    }
+
+    /// write the history to disk if it changed since it was last saved
+    pub fn save_history(&mut self) {
+        if self.history_changed.take().is_some() {
+            self.history.save();
+        }
+    }
+
+    /// show the panel, or hide it if it is already shown
+    pub fn toggle_panel(&mut self, panel: Panel) {
+        self.panel = if self.panel == Some(panel) {
+            None
+        } else {
+            Some(panel)
+        };
     }
 }
@@ -1,11 +1,55 @@
 use crate::{
-    components::keypress::KeyPressHandler, helpers::component_trait::Subscription, update::Message,
+    components::keypress::KeyPressHandler,
+    helpers::component_trait::Subscription,
+    state::SETTINGS_SAVE_DELAY,
+    update::{playlist_message::PlaylistMessage, Message},
     State,
 };
 
 pub fn subscriptions(state: &State) -> iced::Subscription<Message> {
+    // a drag that is released outside of the playlist is cancelled
+    let drag = if state.playlist_drag.is_some() {
+        iced::event::listen_with(|event, _| match event {
+            iced::Event::Mouse(iced::mouse::Event::ButtonReleased(iced::mouse::Button::Left)) => {
+                Some(Message::Playlist(PlaylistMessage::DragEnd))
+            }
+            _ => None,
+        })
+    } else {
+        iced::Subscription::none()
+    };
+
+    // the settings are saved once they stop changing, not on every step of a slider
+    let save_settings = if state.settings_changed.is_some() {
+        iced::time::every(SETTINGS_SAVE_DELAY).map(Message::SaveSettings)
+    } else {
+        iced::Subscription::none()
+    };
+
+    let save_history = if state.history_changed.is_some() {
+        iced::time::every(SETTINGS_SAVE_DELAY).map(Message::SaveHistory)
+    } else {
+        iced::Subscription::none()
+    };
+
     iced::Subscription::batch(vec![
+        drag,
+        save_settings,
+        save_history,
         KeyPressHandler::subscription(state, ()),
+        iced::event::listen_with(|event, _| match event {
+            iced::Event::Window(_, iced::window::Event::Resized { width, height }) => {
+                Some(Message::WindowResized(width, height))
+            }
+            iced::Event::Window(_, iced::window::Event::CloseRequested) => {
+                Some(Message::CloseRequested)
+            }
+            iced::Event::Window(_, iced::window::Event::FileDropped(path)) => {
+                Some(Message::FileDropped(path))
+            }
+            _ => None,
+        }),
         state.player_handler.subscriptions().map(Message::Video),
+        state.player_handler.probe_subscriptions().map(Message::Video),
     ])
 }
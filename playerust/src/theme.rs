@@ -1,19 +1,27 @@
 mod application;
 mod button;
 mod container;
+mod scrollable;
 mod slider;
 mod svg;
 mod text;
+mod text_input;
+mod video_controls;
 
 pub use application::Application;
 pub use button::Button;
 pub use container::Container;
+pub use scrollable::Scrollable;
 pub use slider::Slider;
 pub use svg::Svg;
 pub use text::Text;
+pub use text_input::TextInput;
+pub use video_controls::VideoControls;
 
 use iced::Color;
 
+use crate::config::settings::ThemeName;
+
 /// color macro for use rgb or rgba with 255 instead of 1
 macro_rules! color {
     ($red:expr, $green:expr, $blue:expr) => {
@@ -73,6 +81,31 @@ impl Theme {
     };
 }
 
+impl Theme {
+    pub const LIGHT: Self = Self {
+        text: Color::BLACK,
+        svg: Color::BLACK,
+        background: color!(248, 248, 242),
+        ..Self::NORMAL
+    };
+
+    pub const DARK: Self = Self {
+        text: color!(248, 248, 242),
+        svg: color!(248, 248, 242),
+        ..Self::NORMAL
+    };
+}
+
+impl From<ThemeName> for Theme {
+    fn from(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Default => Self::NORMAL,
+            ThemeName::Light => Self::LIGHT,
+            ThemeName::Dark => Self::DARK,
+        }
+    }
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Self::NORMAL
@@ -0,0 +1,60 @@
+use iced::{
+    widget::{container, scrollable},
+    Border, Color,
+};
+
+use super::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Scrollable {
+    #[default]
+    Default,
+}
+
+impl scrollable::StyleSheet for Theme {
+    type Style = Scrollable;
+
+    fn active(&self, _style: &Self::Style) -> scrollable::Appearance {
+        scrollable::Appearance {
+            container: container::Appearance::default(),
+            scrollbar: scrollable::Scrollbar {
+                background: None,
+                border: Border::with_radius(2.0),
+                scroller: scrollable::Scroller {
+                    color: self.comment,
+                    border: Border::with_radius(2.0),
+                },
+            },
+            gap: None,
+        }
+    }
+
+    fn hovered(
+        &self,
+        style: &Self::Style,
+        is_mouse_over_scrollbar: bool,
+    ) -> scrollable::Appearance {
+        let active = self.active(style);
+        if !is_mouse_over_scrollbar {
+            return active;
+        }
+
+        scrollable::Appearance {
+            scrollbar: scrollable::Scrollbar {
+                background: Some(
+                    Color {
+                        a: 0.3,
+                        ..self.currant_line
+                    }
+                    .into(),
+                ),
+                scroller: scrollable::Scroller {
+                    color: self.light_blue,
+                    ..active.scrollbar.scroller
+                },
+                ..active.scrollbar
+            },
+            ..active
+        }
+    }
+}
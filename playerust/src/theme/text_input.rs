@@ -0,0 +1,74 @@
+use iced::{widget::text_input, Border, Color};
+
+use super::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextInput {
+    #[default]
+    Default,
+    /// the value could not be used
+    Error,
+}
+
+impl Theme {
+    fn text_input_border(&self, style: &TextInput, focused: bool) -> Color {
+        match style {
+            TextInput::Error => self.red,
+            TextInput::Default if focused => self.light_blue,
+            TextInput::Default => self.comment,
+        }
+    }
+}
+
+impl text_input::StyleSheet for Theme {
+    type Style = TextInput;
+
+    fn active(&self, style: &Self::Style) -> text_input::Appearance {
+        text_input::Appearance {
+            background: self.foreground.into(),
+            border: Border {
+                color: self.text_input_border(style, false),
+                width: 1.0,
+                radius: 2.0.into(),
+            },
+            icon_color: self.comment,
+        }
+    }
+
+    fn focused(&self, style: &Self::Style) -> text_input::Appearance {
+        text_input::Appearance {
+            border: Border {
+                color: self.text_input_border(style, true),
+                width: 1.0,
+                radius: 2.0.into(),
+            },
+            ..self.active(style)
+        }
+    }
+
+    fn placeholder_color(&self, _style: &Self::Style) -> Color {
+        self.comment
+    }
+
+    fn value_color(&self, _style: &Self::Style) -> Color {
+        Color::BLACK
+    }
+
+    fn disabled_color(&self, _style: &Self::Style) -> Color {
+        self.comment
+    }
+
+    fn selection_color(&self, _style: &Self::Style) -> Color {
+        Color {
+            a: 0.4,
+            ..self.light_blue
+        }
+    }
+
+    fn disabled(&self, style: &Self::Style) -> text_input::Appearance {
+        text_input::Appearance {
+            background: self.currant_line.into(),
+            ..self.active(style)
+        }
+    }
+}
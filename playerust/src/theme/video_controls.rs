@@ -0,0 +1,36 @@
+use iced::color;
+use iced_video::style::{Appearance, StyleSheet};
+
+use super::Theme;
+
+/*
+ * Video Controls
+ */
+#[derive(Clone, Copy, Default)]
+pub enum VideoControls {
+    /// light controls matching the menu and the playlist around the video
+    #[default]
+    Window,
+    /// dark controls over the video in fullscreen
+    Fullscreen,
+}
+
+impl StyleSheet for Theme {
+    type Style = VideoControls;
+
+    fn appearance(&self, style: &Self::Style) -> Appearance {
+        let appearance = match style {
+            VideoControls::Window => Appearance {
+                background: color!(242, 241, 236, 0.9),
+                ..Appearance::light()
+            },
+            VideoControls::Fullscreen => Appearance::dark(),
+        };
+
+        Appearance {
+            rail_filled: self.light_blue,
+            handle_color: self.light_blue,
+            ..appearance
+        }
+    }
+}
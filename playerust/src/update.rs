@@ -1,19 +1,27 @@
 pub mod menu_event;
 pub mod player_event;
+pub mod playlist_message;
 use std::path::PathBuf;
 
-use iced::Command;
-use iced_video::{
-    viewer::ControlEvent,
-     PlayerMessage,
-     BasicPlayer,
-};
+use iced::{time::Instant, window, Command};
+use iced_video::{viewer::ControlEvent, AdvancedPlayer, PlayerMessage};
 
-use crate::{helpers::component_trait::Update, State};
+use crate::{
+    config::settings::ThemeName,
+    helpers::{
+        component_trait::Update,
+        dropped::{file_dropped, open_dropped},
+        playlist::playlist_event,
+        source::{remember_position, set_source},
+    },
+    state::{Panel, SETTINGS_SAVE_DELAY},
+    State,
+};
 
 use self::{
     menu_event::{menu_event, MenuEvent},
     player_event::control_event,
+    playlist_message::{playlist_message, PlaylistMessage},
 };
 
 #[derive(Clone, Debug)]
@@ -22,23 +30,106 @@ pub enum Message {
     Video(PlayerMessage),
     ControlEvent(ControlEvent),
     MenuEvent(MenuEvent),
+    Playlist(PlaylistMessage),
     SetUri(String),
+    TogglePanel(Panel),
+    SetTheme(ThemeName),
+    WindowResized(u32, u32),
+    /// write the settings to disk if they stopped changing
+    SaveSettings(Instant),
+    /// write the watch history to disk if it stopped changing
+    SaveHistory(Instant),
+    CloseRequested,
+    FileDropped(PathBuf),
+    /// open the files dropped together
+    OpenDropped,
+    /// seek to where the file was left off
+    Resume,
+    DismissResume,
+    SetResumeThreshold(u64),
+    ClearHistory,
     None(()),
 }
 
 pub fn update(state: &mut State, message: Message) -> iced::Command<Message> {
     match message {
         Message::Video(event) => {
-            state.player_handler.handle_event(event);
+            let started = matches!(event, PlayerMessage::Player(..));
+            if let Some(event) = state.player_handler.handle_event(event) {
+                playlist_event(state, event);
+            }
+            if started {
+                if let Some(player) = state.player_handler.get_player("main player") {
+                    state.settings.apply(player);
+                    state.overrides.apply(player);
+                    if let Some(start) = state.start_at.take() {
+                        if let Err(err) = player.seek(start) {
+                            log::error!("could not seek to the start: {:?}", err);
+                        }
+                    }
+                }
+            }
         }
         Message::ControlEvent(event) => return control_event(state, event),
         Message::None(_) => (),
         Message::MenuEvent(event) => return menu_event(state, event),
-        Message::SetUri(uri) => {
-            if let Some(player) = state.player_handler.get_player_mut("main player") {
-                player.set_source(&PathBuf::from(uri));
+        Message::Playlist(message) => playlist_message(state, message),
+        Message::SetUri(uri) => set_source(state, uri),
+        Message::TogglePanel(panel) => state.toggle_panel(panel),
+        Message::SetTheme(theme) => {
+            state.settings.theme = theme;
+            state.settings_changed();
+        }
+        Message::WindowResized(width, height) => {
+            let window_size = (width as f32, height as f32);
+            // the size in fullscreen is not the size to restore
+            if !state.fullscreen && state.settings.window_size != window_size {
+                state.settings.window_size = window_size;
+                state.settings_changed();
+            }
+        }
+        Message::SaveSettings(now) => {
+            if state.settings_changed.is_some_and(|changed| {
+                now.saturating_duration_since(changed) >= SETTINGS_SAVE_DELAY
+            }) {
+                state.save_settings();
             }
         }
+        Message::SaveHistory(now) => {
+            if state.history_changed.is_some_and(|changed| {
+                now.saturating_duration_since(changed) >= SETTINGS_SAVE_DELAY
+            }) {
+                state.save_history();
+            }
+        }
+        Message::CloseRequested => {
+            state.save_settings();
+            remember_position(state);
+            state.save_history();
+            return window::close(window::Id::MAIN);
+        }
+        Message::FileDropped(path) => return file_dropped(state, path),
+        Message::OpenDropped => open_dropped(state),
+        Message::Resume => {
+            if let (Some(position), Some(player)) = (
+                state.resume.take(),
+                state.player_handler.get_player("main player"),
+            ) {
+                if let Err(err) = player.seek(position) {
+                    log::error!("could not resume: {:?}", err);
+                }
+            }
+        }
+        Message::DismissResume => state.resume = None,
+        Message::SetResumeThreshold(threshold) => {
+            state.settings.resume_threshold = threshold;
+            state.settings_changed();
+        }
+        Message::ClearHistory => {
+            state.history.clear();
+            state.history_changed();
+            state.resume = None;
+        }
         Message::KeyBoardEvent(event) => {
             return crate::components::keypress::KeyPressHandler::update(state, event)
         }
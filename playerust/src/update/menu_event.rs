@@ -1,10 +1,20 @@
 use std::path::PathBuf;
 
-use iced::Command;
-use iced_video::BasicPlayer;
+use iced::{widget::text_input, Command};
+use iced_video::playlist::PlaylistFormat;
 use rfd::AsyncFileDialog;
 
-use crate::{state::State, helpers::open_file::open_file};
+use crate::{
+    config::recent,
+    helpers::{
+        open_file::open_file,
+        playlist::{load_playlist, open_playlist, save_playlist, write_playlist},
+        source::set_source,
+        uri::to_uri,
+    },
+    state::{Panel, State},
+    view::URL_INPUT,
+};
 
 use super::Message;
 
@@ -12,6 +22,18 @@ use super::Message;
 pub enum MenuEvent {
     OpenFileDialog,
     OpenFile(Option<String>),
+    OpenUrlDialog,
+    UrlInput(String),
+    OpenUrl,
+    OpenPlaylistDialog,
+    OpenPlaylist(Option<PathBuf>),
+    SavePlaylistDialog,
+    SavePlaylist(Option<PathBuf>),
+    /// show the recently opened files, or hide them if they are shown
+    ToggleRecent,
+    /// open the recently opened file at the index, the most recent first
+    OpenRecent(usize),
+    ClearRecent,
 }
 
 pub fn menu_event(state: &mut State, event: MenuEvent) -> iced::Command<Message> {
@@ -23,11 +45,69 @@ pub fn menu_event(state: &mut State, event: MenuEvent) -> iced::Command<Message>
         }
         MenuEvent::OpenFile(file) => {
             if let Some(uri) = file {
-                if let Some(player) = state.player_handler.get_player_mut("main player") {
-                    player.set_source(&PathBuf::from(uri)).unwrap();
+                set_source(state, uri);
+            }
+        }
+        MenuEvent::OpenUrlDialog => {
+            state.panel = Some(Panel::OpenUrl);
+            state.url_error = None;
+            return text_input::focus(text_input::Id::new(URL_INPUT));
+        }
+        MenuEvent::UrlInput(input) => {
+            state.url_input = input;
+            state.url_error = None;
+        }
+        MenuEvent::OpenUrl => match to_uri(&state.url_input) {
+            Ok(uri) => {
+                state.panel = None;
+                state.url_input.clear();
+                set_source(state, uri);
+            }
+            Err(err) => state.url_error = Some(err),
+        },
+        MenuEvent::OpenPlaylistDialog => {
+            return Command::perform(open_playlist(), |path| {
+                Message::MenuEvent(MenuEvent::OpenPlaylist(path))
+            })
+        }
+        MenuEvent::OpenPlaylist(path) => {
+            if let Some(path) = path {
+                load_playlist(state, &path);
+            }
+        }
+        MenuEvent::SavePlaylistDialog => {
+            return Command::perform(save_playlist(), |path| {
+                Message::MenuEvent(MenuEvent::SavePlaylist(path))
+            })
+        }
+        MenuEvent::SavePlaylist(path) => {
+            if let Some(path) = path {
+                write_playlist(state, &path);
+            }
+        }
+        MenuEvent::ToggleRecent => {
+            let _ = state.recent.prune();
+            state.toggle_panel(Panel::Recent);
+        }
+        MenuEvent::OpenRecent(index) => {
+            let Some(uri) = state.recent.files().get(index).cloned() else {
+                return Command::none();
+            };
+            if !recent::exists(&uri) {
+                log::error!("{} no longer exists", uri);
+                let _ = state.recent.prune();
+                return Command::none();
+            }
+
+            state.panel = None;
+            match recent::local_path(&uri) {
+                Some(path) if PlaylistFormat::from_path(&path).is_some() => {
+                    load_playlist(state, &path)
                 }
+                _ => set_source(state, uri),
             }
         }
+        MenuEvent::ClearRecent => state.recent.clear(),
     }
     Command::none()
 }
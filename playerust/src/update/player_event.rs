@@ -1,38 +1,76 @@
-use std::time::Duration;
-
 use iced::Command;
 use iced_video::{viewer::ControlEvent, BasicPlayer, AdvancedPlayer};
 
-use crate::state::State;
+use crate::{
+    helpers::{fullscreen::set_fullscreen, source::remember_position},
+    state::State,
+};
 
 use super::Message;
 
 pub fn control_event(state: &mut State, event: ControlEvent) -> iced::Command<Message> {
+    if let ControlEvent::ToggleFullscreen | ControlEvent::DoubleClick = event {
+        return set_fullscreen(state, !state.fullscreen);
+    }
+
+    let settings = state.settings.clone();
+    // the toggles flip what the player is doing, it differs from the settings when the
+    // command line overrides them for this session
+    let player = state.player_handler.get_player("main player");
+    match event {
+        ControlEvent::Volume(volume) => state.settings.volume = volume,
+        ControlEvent::ToggleMute => {
+            state.settings.muted = !player.map_or(state.settings.muted, |player| player.get_muted())
+        }
+        ControlEvent::Speed(speed) => state.settings.playback_rate = speed,
+        ControlEvent::ToggleLoop => {
+            state.settings.looping =
+                !player.map_or(state.settings.looping, |player| player.get_looping())
+        }
+        _ => {}
+    }
+    if state.settings != settings {
+        state.settings_changed();
+    }
+
+    let mut paused = false;
     if let Some(player) = state.player_handler.get_player_mut("main player") {
         match event {
             ControlEvent::Play => player
                 .play(),
 
-            ControlEvent::Pause => player
-                .pause(),
-            ControlEvent::ToggleMute => {
-                if player.get_muted() {
-                    player.set_muted(false)
-                } else {
-                    player.set_muted(true)
-                }
+            ControlEvent::Pause => {
+                player.pause();
+                paused = true;
             }
+            ControlEvent::ToggleMute => player.set_muted(state.settings.muted),
             ControlEvent::Volume(volume) => player.set_volume(volume),
-            ControlEvent::Seek(seek_amount) => {
-                state.seek = Some(seek_amount as u64);
-            }
-            ControlEvent::Released => {
-                player
-                    .seek(Duration::from_secs(state.seek.unwrap()))
-                    .unwrap_or_else(|err| println!("Error seeking: {:?}", err));
-                state.seek = None;
+            ControlEvent::SeekTo(position) => player
+                .seek(position)
+                .unwrap_or_else(|err| println!("Error seeking: {:?}", err)),
+            ControlEvent::Click => {
+                if player.is_playing() {
+                    player.pause();
+                    paused = true;
+                } else {
+                    player.play()
+                }
             }
+            ControlEvent::Speed(speed) => player
+                .set_playback_rate(speed)
+                .unwrap_or_else(|err| println!("Error setting speed: {:?}", err)),
+            ControlEvent::ToggleLoop => player.set_looping(state.settings.looping),
+            ControlEvent::DoubleClick
+            | ControlEvent::ToggleFullscreen
+            | ControlEvent::Drag(_)
+            | ControlEvent::DragReleased
+            | ControlEvent::Track(_)
+            | ControlEvent::Chapter(_) => {}
         };
     }
+
+    if paused {
+        remember_position(state);
+    }
     Command::none()
 }
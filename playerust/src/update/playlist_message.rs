@@ -0,0 +1,85 @@
+use iced_video::playlist::Repeat;
+
+use crate::{
+    helpers::{playlist::playlist_event, source::remember_position},
+    state::State,
+};
+
+#[derive(Clone, Debug)]
+pub enum PlaylistMessage {
+    Toggle,
+    Play(usize),
+    Remove(usize),
+    Next,
+    Previous,
+    ToggleShuffle,
+    CycleRepeat,
+    /// an item was pressed, it is played or moved once released
+    DragStart(usize),
+    DragOver(usize),
+    Drop(usize),
+    DragEnd,
+}
+
+pub fn playlist_message(state: &mut State, message: PlaylistMessage) {
+    match message {
+        PlaylistMessage::Toggle => state.show_playlist = !state.show_playlist,
+        PlaylistMessage::Play(index) => {
+            remember_position(state);
+            if let Some(event) = state.player_handler.play("main player", index) {
+                playlist_event(state, event);
+            }
+        }
+        PlaylistMessage::Next => {
+            remember_position(state);
+            if let Some(event) = state.player_handler.next("main player") {
+                playlist_event(state, event);
+            }
+        }
+        PlaylistMessage::Previous => {
+            remember_position(state);
+            if let Some(event) = state.player_handler.previous("main player") {
+                playlist_event(state, event);
+            }
+        }
+        PlaylistMessage::Remove(index) => {
+            if let Some(playlist) = state.player_handler.get_playlist_mut("main player") {
+                let _ = playlist.remove(index);
+            }
+            state.player_handler.queue_next("main player");
+        }
+        PlaylistMessage::ToggleShuffle => {
+            if let Some(playlist) = state.player_handler.get_playlist_mut("main player") {
+                playlist.set_shuffle(!playlist.shuffle());
+            }
+            state.player_handler.queue_next("main player");
+        }
+        PlaylistMessage::CycleRepeat => {
+            if let Some(playlist) = state.player_handler.get_playlist_mut("main player") {
+                playlist.set_repeat(match playlist.repeat() {
+                    Repeat::Off => Repeat::All,
+                    Repeat::All => Repeat::One,
+                    Repeat::One => Repeat::Off,
+                });
+            }
+            state.player_handler.queue_next("main player");
+        }
+        PlaylistMessage::DragStart(index) => {
+            state.playlist_drag = Some(index);
+            state.playlist_hover = Some(index);
+        }
+        PlaylistMessage::DragOver(index) => state.playlist_hover = Some(index),
+        PlaylistMessage::Drop(index) => match state.playlist_drag.take() {
+            // pressed and released on the same item
+            Some(from) if from == index => playlist_message(state, PlaylistMessage::Play(index)),
+            Some(from) => {
+                if let Some(playlist) = state.player_handler.get_playlist_mut("main player") {
+                    playlist.move_item(from, index);
+                }
+                state.player_handler.queue_next("main player");
+            }
+            None => {}
+        },
+        PlaylistMessage::DragEnd => state.playlist_drag = None,
+    }
+}
@@ -1,16 +1,29 @@
-mod controls;
+mod help;
 mod image;
 mod menu;
+mod open_url;
+mod playlist;
+mod recent;
+mod resume;
+mod settings;
 
 use iced::widget;
 
+pub use open_url::URL_INPUT;
+pub use playlist::THUMBNAIL_WIDTH;
+
 use crate::{Element, State};
 
 pub fn view(state: &State) -> Element {
-    widget::container(widget::column![
-        menu::menu(state),
-        image::image(state),
-        controls::controls(state)
-    ])
-    .into()
+    if state.fullscreen {
+        return image::image(state);
+    }
+
+    let body: Element = if state.show_playlist {
+        widget::row![image::image(state), playlist::playlist(state)].into()
+    } else {
+        image::image(state)
+    };
+
+    widget::container(widget::column![menu::menu(state), body]).into()
 }
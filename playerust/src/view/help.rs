@@ -0,0 +1,49 @@
+use iced::{widget, Length};
+
+use crate::{
+    config::keybindings::{format_binding, KeyBindings},
+    state::{Panel, State},
+    theme,
+    update::Message,
+    Element,
+};
+
+/// the active keybindings, shown over the video
+pub fn help(state: &State) -> Element {
+    let mut content = widget::column![widget::row![
+        widget::text("Keybindings").size(20).width(Length::Fill),
+        widget::button(widget::text("Close")).on_press(Message::TogglePanel(Panel::Help)),
+    ]]
+    .spacing(4);
+
+    for error in &state.keybinding_errors {
+        content = content.push(
+            widget::text(error)
+                .size(14)
+                .style(theme::Text::Custom(|theme| widget::text::Appearance {
+                    color: Some(theme.red),
+                })),
+        );
+    }
+
+    for (action, keys) in state.keybindings.actions() {
+        let keys: Vec<String> = keys.into_iter().map(format_binding).collect();
+        content = content.push(widget::row![
+            widget::text(action.description())
+                .size(14)
+                .width(Length::Fill),
+            widget::text(keys.join(", ")).size(14),
+        ]);
+    }
+
+    if let Some(path) = KeyBindings::path() {
+        content =
+            content.push(widget::text(format!("Edit {} to change them", path.display())).size(12));
+    }
+
+    widget::container(content)
+        .padding(20)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
@@ -1,43 +1,70 @@
 use iced::{widget, Background, Color, Length};
-use iced_video::{viewer::ControlEvent, BasicPlayer};
+use iced_video::{
+    overlay::Overlay,
+    video_frame::VideoFrame,
+    viewer::{Control, VideoView},
+};
 
-use crate::{state::State, theme, update::Message, Element};
+use crate::{
+    state::{Panel, State},
+    theme::{self, VideoControls},
+    update::Message,
+    Element,
+};
+
+use super::{help::help, open_url::open_url, recent::recent, resume::resume, settings::settings};
 
 pub fn image(state: &State) -> Element {
-    let image = if let Some(handle) = state.player_handler.get_frame("main player") {
-        iced::widget::image(handle.clone())
-            .height(Length::Fill)
+    let frame = state.player_handler.get_frame("main player");
+
+    let video: Element = match state.player_handler.get_player("main player") {
+        Some(player) => VideoView::new(player, frame, Message::ControlEvent)
             .width(Length::Fill)
-    } else {
-        iced::widget::image(widget::image::Handle::from_pixels(0, 0, vec![]))
             .height(Length::Fill)
+            .fullscreen(state.fullscreen)
+            .controls([
+                Control::PlayPause,
+                Control::Time,
+                Control::Mute,
+                Control::Volume,
+                Control::Space,
+                Control::Speed,
+                Control::Loop,
+                Control::Fullscreen,
+            ])
+            .style(if state.fullscreen {
+                VideoControls::Fullscreen
+            } else {
+                VideoControls::Window
+            })
+            .into(),
+        None => VideoFrame::new(frame.cloned())
             .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+    };
+
+    let modal: Element = match state.panel {
+        Some(Panel::Help) => help(state),
+        Some(Panel::Settings) => settings(state),
+        Some(Panel::OpenUrl) => open_url(state),
+        Some(Panel::Recent) => recent(state),
+        None => match state.resume {
+            Some(position) => resume(position),
+            None => widget::Space::new(Length::Fill, 0).into(),
+        },
     };
 
-    widget::container(
-        widget::button(image)
-            .on_press(
-                if let Some(player) = state.player_handler.get_player("main player") {
-                    if player.is_playing() {
-                        Message::ControlEvent(ControlEvent::Pause)
-                    } else {
-                        Message::ControlEvent(ControlEvent::Play)
-                    }
-                } else {
-                    Message::None(())
-                },
-            )
-            .style(theme::Button::Transparent),
-    )
-    .height(Length::Fill)
-    .width(Length::Fill)
-    .style(theme::Container::Custom(|_theme| {
-        widget::container::Appearance {
-            text_color: None,
-            background: Some(Background::Color(Color::BLACK)),
-            border: iced::Border::default(),
-            shadow: iced::Shadow::default(),
-        }
-    }))
-    .into()
+    widget::container(Overlay::new(video, modal))
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .style(theme::Container::Custom(|_theme| {
+            widget::container::Appearance {
+                text_color: None,
+                background: Some(Background::Color(Color::BLACK)),
+                border: iced::Border::default(),
+                shadow: iced::Shadow::default(),
+            }
+        }))
+        .into()
 }
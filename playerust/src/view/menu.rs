@@ -1,15 +1,28 @@
 use iced::widget;
 
 use crate::{
-    state::State,
-    update::{menu_event::MenuEvent, Message},
+    state::{Panel, State},
+    update::{menu_event::MenuEvent, playlist_message::PlaylistMessage, Message},
     Element,
 };
 
 pub fn menu(_state: &State) -> Element {
-    widget::container(
+    widget::container(widget::row![
         widget::button(widget::text("Open File"))
             .on_press(Message::MenuEvent(MenuEvent::OpenFileDialog)),
-    )
+        widget::button(widget::text("Recent"))
+            .on_press(Message::MenuEvent(MenuEvent::ToggleRecent)),
+        widget::button(widget::text("Open URL"))
+            .on_press(Message::MenuEvent(MenuEvent::OpenUrlDialog)),
+        widget::button(widget::text("Open Playlist"))
+            .on_press(Message::MenuEvent(MenuEvent::OpenPlaylistDialog)),
+        widget::button(widget::text("Save Playlist"))
+            .on_press(Message::MenuEvent(MenuEvent::SavePlaylistDialog)),
+        widget::button(widget::text("Playlist"))
+            .on_press(Message::Playlist(PlaylistMessage::Toggle)),
+        widget::button(widget::text("Settings")).on_press(Message::TogglePanel(Panel::Settings)),
+        widget::button(widget::text("Keybindings")).on_press(Message::TogglePanel(Panel::Help)),
+    ]
+    .spacing(5))
     .into()
 }
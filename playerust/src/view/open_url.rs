@@ -0,0 +1,60 @@
+use iced::{widget, Alignment, Length};
+
+use crate::{
+    helpers::uri::SCHEMES,
+    state::{Panel, State},
+    theme,
+    update::{menu_event::MenuEvent, Message},
+    Element,
+};
+
+/// the id of the url input, focused when the dialog is opened
+pub const URL_INPUT: &str = "open url";
+
+/// ask for a url or path to open, shown over the video
+pub fn open_url(state: &State) -> Element {
+    let mut content = widget::column![
+        widget::row![
+            widget::text("Open URL").size(20).width(Length::Fill),
+            widget::button(widget::text("Close")).on_press(Message::TogglePanel(Panel::OpenUrl)),
+        ],
+        widget::row![
+            widget::text_input("https://example.com/video.mp4", &state.url_input)
+                .id(widget::text_input::Id::new(URL_INPUT))
+                .on_input(|input| Message::MenuEvent(MenuEvent::UrlInput(input)))
+                .on_submit(Message::MenuEvent(MenuEvent::OpenUrl))
+                .style(if state.url_error.is_some() {
+                    theme::TextInput::Error
+                } else {
+                    theme::TextInput::Default
+                })
+                .padding(5),
+            widget::button(widget::text("Open")).on_press(Message::MenuEvent(MenuEvent::OpenUrl)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(8);
+
+    if let Some(error) = &state.url_error {
+        content = content.push(
+            widget::text(error)
+                .size(14)
+                .style(theme::Text::Custom(|theme| widget::text::Appearance {
+                    color: Some(theme.red),
+                })),
+        );
+    }
+    content = content.push(
+        widget::text(format!(
+            "Supported: {} urls and paths to local files",
+            SCHEMES.join(", ")
+        ))
+        .size(12),
+    );
+
+    widget::container(content)
+        .padding(20)
+        .width(Length::Fill)
+        .into()
+}
@@ -0,0 +1,136 @@
+use iced::{widget, Alignment, Background, Length};
+use iced_video::{helpers::helper_functions::secs_to_hhmmss, playlist::Repeat};
+
+use crate::{
+    state::State,
+    theme::{self, Theme},
+    update::{playlist_message::PlaylistMessage, Message},
+    Element,
+};
+
+/// the width of the thumbnails in the list
+pub const THUMBNAIL_WIDTH: u16 = 64;
+
+/// the queued files next to the video
+pub fn playlist(state: &State) -> Element {
+    let message = |message| Message::Playlist(message);
+    let playlist = state.player_handler.get_playlist("main player");
+
+    let (shuffle, repeat) = playlist.map_or((false, Repeat::Off), |playlist| {
+        (playlist.shuffle(), playlist.repeat())
+    });
+    let toggle = |name: &'static str, on: bool, event: PlaylistMessage| {
+        widget::button(widget::text(name))
+            .style(if on {
+                theme::Button::Normal
+            } else {
+                theme::Button::Transparent
+            })
+            .on_press_maybe(playlist.map(|_| message(event)))
+    };
+
+    let header = widget::row![
+        widget::text("Playlist").size(20).width(Length::Fill),
+        widget::button(widget::text("<"))
+            .on_press_maybe(playlist.map(|_| message(PlaylistMessage::Previous))),
+        widget::button(widget::text(">"))
+            .on_press_maybe(playlist.map(|_| message(PlaylistMessage::Next))),
+        toggle("Shuffle", shuffle, PlaylistMessage::ToggleShuffle),
+        toggle(
+            match repeat {
+                Repeat::Off => "Repeat",
+                Repeat::All => "Repeat all",
+                Repeat::One => "Repeat one",
+            },
+            repeat != Repeat::Off,
+            PlaylistMessage::CycleRepeat,
+        ),
+    ]
+    .spacing(5)
+    .align_items(Alignment::Center);
+
+    let items: Element = match playlist.filter(|playlist| !playlist.is_empty()) {
+        Some(playlist) => {
+            let rows = playlist.items().iter().enumerate().map(|(index, item)| {
+                let info = state.player_handler.get_media_info(&item.uri);
+                let thumbnail: Element = match info.and_then(|info| info.thumbnail.clone()) {
+                    Some(thumbnail) => widget::image(thumbnail).width(THUMBNAIL_WIDTH).into(),
+                    None => widget::Space::new(THUMBNAIL_WIDTH, THUMBNAIL_WIDTH / 16 * 9).into(),
+                };
+                let duration = info
+                    .and_then(|info| info.duration)
+                    .or(item.duration)
+                    .map_or_else(
+                        || "--:--".into(),
+                        |duration| secs_to_hhmmss(duration.as_secs()),
+                    );
+
+                let row = widget::row![
+                    thumbnail,
+                    widget::column![
+                        widget::text(item.name()).size(14),
+                        widget::text(duration).size(12),
+                    ]
+                    .width(Length::Fill),
+                    widget::button(widget::text("x"))
+                        .style(theme::Button::Transparent)
+                        .on_press(message(PlaylistMessage::Remove(index))),
+                ]
+                .spacing(8)
+                .align_items(Alignment::Center);
+
+                let highlight = highlight(state, playlist.current(), index);
+                widget::mouse_area(
+                    widget::container(row)
+                        .padding(4)
+                        .width(Length::Fill)
+                        .style(theme::Container::Custom(highlight)),
+                )
+                .on_press(message(PlaylistMessage::DragStart(index)))
+                .on_enter(message(PlaylistMessage::DragOver(index)))
+                .on_release(message(PlaylistMessage::Drop(index)))
+                .into()
+            });
+
+            widget::scrollable(widget::Column::with_children(rows).spacing(2))
+                .height(Length::Fill)
+                .into()
+        }
+        None => widget::text("The playlist is empty, open one from the menu or drop it here")
+            .size(14)
+            .into(),
+    };
+
+    widget::container(widget::column![header, items].spacing(10))
+        .width(300)
+        .height(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+/// the background of an item, showing the current item and where a dragged item goes
+fn highlight(
+    state: &State,
+    current: Option<usize>,
+    index: usize,
+) -> fn(&Theme) -> widget::container::Appearance {
+    let dragging = state.playlist_drag.is_some_and(|from| from != index);
+    if dragging && state.playlist_hover == Some(index) {
+        |theme| background(theme.comment)
+    } else if state.playlist_drag == Some(index) {
+        |theme| background(theme.currant_line)
+    } else if current == Some(index) {
+        |theme| background(theme.light_blue)
+    } else {
+        |_| widget::container::Appearance::default()
+    }
+}
+
+fn background(color: iced::Color) -> widget::container::Appearance {
+    widget::container::Appearance {
+        background: Some(Background::Color(color)),
+        border: iced::Border::with_radius(2.0),
+        ..Default::default()
+    }
+}
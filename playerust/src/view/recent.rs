@@ -0,0 +1,45 @@
+use iced::{widget, Length};
+
+use crate::{
+    config::recent,
+    state::State,
+    theme,
+    update::{menu_event::MenuEvent, Message},
+    Element,
+};
+
+/// the recently opened files, dropped down under the menu
+pub fn recent(state: &State) -> Element {
+    let mut content = widget::column![].spacing(2).width(Length::Fixed(300.0));
+
+    if state.recent.is_empty() {
+        content = content.push(widget::text("No recent files").size(14));
+    }
+    for (index, uri) in state.recent.files().iter().enumerate() {
+        let number = if index < 9 {
+            format!("{}", index + 1)
+        } else {
+            String::new()
+        };
+        content = content.push(
+            widget::button(widget::row![
+                widget::text(number).size(14).width(20),
+                widget::text(recent::name(uri)).size(14),
+            ])
+            .style(theme::Button::Transparent)
+            .width(Length::Fill)
+            .on_press(Message::MenuEvent(MenuEvent::OpenRecent(index))),
+        );
+    }
+    if !state.recent.is_empty() {
+        content = content.push(
+            widget::button(widget::text("Clear").size(14))
+                .on_press(Message::MenuEvent(MenuEvent::ClearRecent)),
+        );
+    }
+
+    widget::container(content)
+        .padding(5)
+        .style(theme::Container::Box)
+        .into()
+}
@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use iced::{widget, Alignment, Length};
+use iced_video::helpers::helper_functions::secs_to_hhmmss;
+
+use crate::{update::Message, Element};
+
+/// offer to resume the file where it was left off
+pub fn resume(position: Duration) -> Element<'static> {
+    widget::container(
+        widget::row![
+            widget::text(format!(
+                "Resume from {}?",
+                secs_to_hhmmss(position.as_secs())
+            ))
+            .width(Length::Fill),
+            widget::button(widget::text("Resume")).on_press(Message::Resume),
+            widget::button(widget::text("Start over")).on_press(Message::DismissResume),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .into()
+}
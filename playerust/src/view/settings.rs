@@ -0,0 +1,118 @@
+use iced::{widget, Alignment, Length};
+use iced_video::viewer::ControlEvent;
+
+use crate::{
+    config::settings::{Settings, ThemeName},
+    state::{Panel, State},
+    theme,
+    update::Message,
+    Element,
+};
+
+/// the settings kept between launches, shown over the video
+pub fn settings(state: &State) -> Element {
+    let settings = &state.settings;
+
+    let toggle = |on: bool, event: ControlEvent| {
+        widget::button(widget::text(if on { "On" } else { "Off" }))
+            .style(if on {
+                theme::Button::Normal
+            } else {
+                theme::Button::Transparent
+            })
+            .on_press(Message::ControlEvent(event))
+    };
+
+    let themes = ThemeName::ALL.into_iter().map(|name| {
+        widget::button(widget::text(name.to_string()))
+            .style(if settings.theme == name {
+                theme::Button::Normal
+            } else {
+                theme::Button::Transparent
+            })
+            .on_press(Message::SetTheme(name))
+            .into()
+    });
+
+    let mut content = widget::column![
+        widget::row![
+            widget::text("Settings").size(20).width(Length::Fill),
+            widget::button(widget::text("Close")).on_press(Message::TogglePanel(Panel::Settings)),
+        ],
+        setting(
+            "Volume",
+            widget::row![
+                widget::slider(0.0..=1.0, settings.volume, |volume| {
+                    Message::ControlEvent(ControlEvent::Volume(volume))
+                })
+                .step(0.05)
+                .width(150)
+                .style(theme::Slider::Volume),
+                widget::text(format!("{:.0}%", settings.volume * 100.0)),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        ),
+        setting("Mute", toggle(settings.muted, ControlEvent::ToggleMute)),
+        setting(
+            "Playback speed",
+            widget::row![
+                widget::button(widget::text("-")).on_press(Message::ControlEvent(
+                    ControlEvent::Speed((settings.playback_rate - 0.25).max(0.25))
+                )),
+                widget::text(format!("{:.2}x", settings.playback_rate)),
+                widget::button(widget::text("+")).on_press(Message::ControlEvent(
+                    ControlEvent::Speed((settings.playback_rate + 0.25).min(2.0))
+                )),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        ),
+        setting("Loop", toggle(settings.looping, ControlEvent::ToggleLoop)),
+        setting("Theme", widget::Row::with_children(themes).spacing(5)),
+        setting(
+            "Resume after",
+            widget::row![
+                widget::button(widget::text("-")).on_press(Message::SetResumeThreshold(
+                    settings.resume_threshold.saturating_sub(15)
+                )),
+                widget::text(format!("{} seconds", settings.resume_threshold)),
+                widget::button(widget::text("+"))
+                    .on_press(Message::SetResumeThreshold(settings.resume_threshold + 15)),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        ),
+        setting(
+            "Watch history",
+            widget::button(widget::text("Clear history"))
+                .on_press_maybe((!state.history.is_empty()).then_some(Message::ClearHistory)),
+        ),
+        setting(
+            "Window size",
+            widget::text(format!(
+                "{} x {}",
+                settings.window_size.0, settings.window_size.1
+            )),
+        ),
+    ]
+    .spacing(8);
+
+    if let Some(path) = Settings::path() {
+        content = content
+            .push(widget::text(format!("Settings are saved to {}", path.display())).size(12));
+    }
+
+    widget::container(content)
+        .padding(20)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+/// a row with the name of a setting and its control
+fn setting<'a>(name: &'a str, control: impl Into<Element<'a>>) -> Element<'a> {
+    widget::row![widget::text(name).width(200), control.into()]
+        .align_items(Alignment::Center)
+        .into()
+}
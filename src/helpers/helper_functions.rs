@@ -1,14 +1,205 @@
 //! Helper functions for the application
 
-/// convert seconds to hh:mm:ss
+use std::time::Duration;
+
+/// How a position in a video is written out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// hh:mm:ss
+    #[default]
+    Seconds,
+    /// hh:mm:ss.mmm
+    Milliseconds,
+    /// the number of frames, needs the framerate
+    Frames,
+    /// SMPTE timecode hh:mm:ss:ff, needs the framerate.
+    /// drop frame timecode (hh:mm:ss;ff) is used for 29.97 and 59.94 fps
+    Timecode,
+}
+
+/// convert seconds to hh:mm:ss, the hours are left out under an hour.
+/// times under two minutes used to be written without their minutes, 65 is now `1:05`
 pub fn secs_to_hhmmss(seconds: u64) -> String {
     let (hours, seconds) = (seconds / 3600, seconds % 3600);
     let (minutes, seconds) = (seconds / 60, seconds % 60);
     if hours > 0 {
         format!("{}:{:02}:{:02}", hours, minutes, seconds)
-    } else if minutes > 1 {
-        format!("{}:{:02}", minutes, seconds)
     } else {
-        format!("0:{:02}", seconds)
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// convert a duration to hh:mm:ss.mmm
+pub fn duration_to_hhmmss_mmm(time: Duration) -> String {
+    format!(
+        "{}.{:03}",
+        secs_to_hhmmss(time.as_secs()),
+        time.subsec_millis()
+    )
+}
+
+/// the number of whole frames shown before the given time
+pub fn duration_to_frames(time: Duration, framerate: f64) -> u64 {
+    (time.as_secs_f64() * framerate + 1e-6).floor() as u64
+}
+
+/// convert a duration to a SMPTE timecode
+/// uses drop frame timecode for NTSC framerates (29.97 and 59.94 fps)
+pub fn duration_to_timecode(time: Duration, framerate: f64) -> String {
+    let nominal = framerate.round().max(1.0) as u64;
+    let mut frame = duration_to_frames(time, framerate);
+
+    let drop_frame = (framerate - nominal as f64).abs() > 0.001 && matches!(nominal, 30 | 60);
+    if drop_frame {
+        // skip the frame numbers dropped at the start of every minute except each tenth
+        let dropped = nominal / 15;
+        let frames_per_minute = nominal * 60 - dropped;
+        let frames_per_ten_minutes = frames_per_minute * 10 + dropped;
+
+        let tens = frame / frames_per_ten_minutes;
+        let rest = frame % frames_per_ten_minutes;
+        frame += dropped * 9 * tens;
+        if rest > dropped {
+            frame += dropped * ((rest - dropped) / frames_per_minute);
+        }
+    }
+
+    let frames = frame % nominal;
+    let seconds = frame / nominal;
+    format!(
+        "{:02}:{:02}:{:02}{}{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        if drop_frame { ';' } else { ':' },
+        frames
+    )
+}
+
+/// write out a position in the given format
+/// falls back to hh:mm:ss.mmm for the frame based formats when the framerate is unknown
+pub fn format_time(time: Duration, format: TimeFormat, framerate: Option<f64>) -> String {
+    match (format, framerate.filter(|framerate| *framerate > 0.0)) {
+        (TimeFormat::Seconds, _) => secs_to_hhmmss(time.as_secs()),
+        (TimeFormat::Frames, Some(framerate)) => duration_to_frames(time, framerate).to_string(),
+        (TimeFormat::Timecode, Some(framerate)) => duration_to_timecode(time, framerate),
+        (TimeFormat::Milliseconds | TimeFormat::Frames | TimeFormat::Timecode, _) => {
+            duration_to_hhmmss_mmm(time)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NTSC: f64 = 30000.0 / 1001.0;
+    const NTSC_60: f64 = 60000.0 / 1001.0;
+
+    /// the time the given frame is shown at
+    fn frame(index: u64, framerate: f64) -> Duration {
+        Duration::from_secs_f64(index as f64 / framerate)
+    }
+
+    #[test]
+    fn secs_to_hhmmss_keeps_the_minutes() {
+        assert_eq!(secs_to_hhmmss(0), "0:00");
+        assert_eq!(secs_to_hhmmss(5), "0:05");
+        // the first minute used to be written as 0:05
+        assert_eq!(secs_to_hhmmss(65), "1:05");
+        assert_eq!(secs_to_hhmmss(600), "10:00");
+        assert_eq!(secs_to_hhmmss(3661), "1:01:01");
+    }
+
+    #[test]
+    fn milliseconds() {
+        assert_eq!(
+            duration_to_hhmmss_mmm(Duration::from_millis(65_042)),
+            "1:05.042"
+        );
+    }
+
+    #[test]
+    fn frames_count_whole_frames() {
+        assert_eq!(duration_to_frames(Duration::ZERO, 25.0), 0);
+        assert_eq!(duration_to_frames(Duration::from_millis(39), 25.0), 0);
+        assert_eq!(duration_to_frames(Duration::from_millis(40), 25.0), 1);
+        assert_eq!(duration_to_frames(frame(1800, NTSC), NTSC), 1800);
+    }
+
+    #[test]
+    fn non_drop_timecode() {
+        assert_eq!(
+            duration_to_timecode(frame(90 * 25 + 12, 25.0), 25.0),
+            "00:01:30:12"
+        );
+        // 23.976 is not a drop frame rate
+        assert_eq!(
+            duration_to_timecode(frame(1440, 24000.0 / 1001.0), 24000.0 / 1001.0),
+            "00:01:00:00"
+        );
+    }
+
+    #[test]
+    fn drop_frame_timecode_29_97() {
+        assert_eq!(duration_to_timecode(frame(0, NTSC), NTSC), "00:00:00;00");
+        assert_eq!(duration_to_timecode(frame(1799, NTSC), NTSC), "00:00:59;29");
+        // frames 00 and 01 are dropped at the start of the minute
+        assert_eq!(duration_to_timecode(frame(1800, NTSC), NTSC), "00:01:00;02");
+        assert_eq!(duration_to_timecode(frame(3597, NTSC), NTSC), "00:01:59;29");
+        assert_eq!(duration_to_timecode(frame(3598, NTSC), NTSC), "00:02:00;02");
+        // but not at the start of every tenth minute
+        assert_eq!(
+            duration_to_timecode(frame(17981, NTSC), NTSC),
+            "00:09:59;29"
+        );
+        assert_eq!(
+            duration_to_timecode(frame(17982, NTSC), NTSC),
+            "00:10:00;00"
+        );
+        assert_eq!(
+            duration_to_timecode(frame(19782, NTSC), NTSC),
+            "00:11:00;02"
+        );
+        assert_eq!(
+            duration_to_timecode(frame(107892, NTSC), NTSC),
+            "01:00:00;00"
+        );
+    }
+
+    #[test]
+    fn drop_frame_timecode_59_94() {
+        assert_eq!(
+            duration_to_timecode(frame(3599, NTSC_60), NTSC_60),
+            "00:00:59;59"
+        );
+        assert_eq!(
+            duration_to_timecode(frame(3600, NTSC_60), NTSC_60),
+            "00:01:00;04"
+        );
+        assert_eq!(
+            duration_to_timecode(frame(35963, NTSC_60), NTSC_60),
+            "00:09:59;59"
+        );
+        assert_eq!(
+            duration_to_timecode(frame(35964, NTSC_60), NTSC_60),
+            "00:10:00;00"
+        );
+    }
+
+    #[test]
+    fn format_time_falls_back_without_a_framerate() {
+        let time = Duration::from_millis(1_500);
+        assert_eq!(format_time(time, TimeFormat::Seconds, None), "0:01");
+        assert_eq!(format_time(time, TimeFormat::Frames, None), "0:01.500");
+        assert_eq!(
+            format_time(time, TimeFormat::Timecode, Some(0.0)),
+            "0:01.500"
+        );
+        assert_eq!(format_time(time, TimeFormat::Frames, Some(24.0)), "36");
+        assert_eq!(
+            format_time(time, TimeFormat::Timecode, Some(24.0)),
+            "00:00:01:12"
+        );
     }
 }
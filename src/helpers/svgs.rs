@@ -56,3 +56,48 @@ pub fn muted_svg() -> svg::Handle {
     </svg>
     ".as_bytes())
 }
+
+/// The loop svg.
+pub fn loop_svg() -> svg::Handle {
+    svg::Handle::from_memory("
+    <svg xmlns='http://www.w3.org/2000/svg' fill='none' stroke='white' stroke-width='1.3' stroke-linecap='round' stroke-linejoin='round' viewBox='0 0 16 16'>
+        <path d='M2.5 8V6.5a2 2 0 0 1 2-2h9m-2-2 2 2-2 2M13.5 8v1.5a2 2 0 0 1-2 2h-9m2 2-2-2 2-2'/>
+    </svg>
+    ".as_bytes())
+}
+
+/// The fullscreen svg.
+pub fn fullscreen_svg() -> svg::Handle {
+    svg::Handle::from_memory("
+    <svg xmlns='http://www.w3.org/2000/svg' fill='none' stroke='white' stroke-width='1.3' stroke-linecap='round' stroke-linejoin='round' viewBox='0 0 16 16'>
+        <path d='M1.5 5.5v-4h4m5 0h4v4m0 5v4h-4m-5 0h-4v-4'/>
+    </svg>
+    ".as_bytes())
+}
+
+/// The exit fullscreen svg.
+pub fn exit_fullscreen_svg() -> svg::Handle {
+    svg::Handle::from_memory("
+    <svg xmlns='http://www.w3.org/2000/svg' fill='none' stroke='white' stroke-width='1.3' stroke-linecap='round' stroke-linejoin='round' viewBox='0 0 16 16'>
+        <path d='M5.5 1.5v4h-4m13 0h-4v-4m0 13v-4h4m-13 0h4v4'/>
+    </svg>
+    ".as_bytes())
+}
+
+/// The previous chapter svg.
+pub fn previous_chapter_svg() -> svg::Handle {
+    svg::Handle::from_memory("
+    <svg xmlns='http://www.w3.org/2000/svg' fill='white' viewBox='0 0 16 16'>
+        <path d='M3.5 3.5a.75.75 0 0 1 1.5 0v9a.75.75 0 0 1-1.5 0zm9 .5v8a.6.6 0 0 1-.92.5L6 8.5a.6.6 0 0 1 0-1l5.58-4a.6.6 0 0 1 .92.5z'/>
+    </svg>
+    ".as_bytes())
+}
+
+/// The next chapter svg.
+pub fn next_chapter_svg() -> svg::Handle {
+    svg::Handle::from_memory("
+    <svg xmlns='http://www.w3.org/2000/svg' fill='white' viewBox='0 0 16 16'>
+        <path d='M11 3.5a.75.75 0 0 1 1.5 0v9a.75.75 0 0 1-1.5 0zM3.5 4v8a.6.6 0 0 0 .92.5L10 8.5a.6.6 0 0 0 0-1l-5.58-4a.6.6 0 0 0-.92.5z'/>
+    </svg>
+    ".as_bytes())
+}
@@ -0,0 +1,592 @@
+//! Keyboard shortcuts for the video controls.
+//! a [`KeyMap`] binds keys to [`Action`]s, the [`KeyHandler`] widget turns the key presses
+//! into messages while it is focused.
+
+use std::time::Duration;
+
+use iced::{
+    advanced::{
+        layout, overlay, renderer,
+        widget::{self, operation::Focusable, tree, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    event,
+    keyboard::{self, key::Named, Key, Modifiers},
+    mouse, touch, Element, Event, Length, Rectangle, Size, Vector,
+};
+use playbin_core::AdvancedPlayer;
+
+use crate::viewer::ControlEvent;
+
+/// How much [`Action::VolumeUp`] and [`Action::VolumeDown`] change the volume.
+const VOLUME_STEP: f64 = 0.1;
+
+/// How much [`Action::SpeedUp`] and [`Action::SpeedDown`] change the playback rate.
+const SPEED_STEP: f64 = 0.25;
+
+/// The slowest and fastest playback rates reached with the keyboard.
+const SPEED_RANGE: (f64, f64) = (0.25, 2.0);
+
+/// Something a key can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// play or pause the video
+    PlayPause,
+    /// seek forward by the given amount
+    SeekForward(Duration),
+    /// seek backward by the given amount
+    SeekBackward(Duration),
+    /// turn the volume up
+    VolumeUp,
+    /// turn the volume down
+    VolumeDown,
+    /// mute and unmute the audio
+    ToggleMute,
+    /// turn looping on and off
+    ToggleLoop,
+    /// play faster
+    SpeedUp,
+    /// play slower
+    SpeedDown,
+    /// play at normal speed
+    ResetSpeed,
+    /// switch fullscreen on and off
+    ToggleFullscreen,
+    /// leave fullscreen, does nothing when not in fullscreen
+    ExitFullscreen,
+}
+
+impl Action {
+    /// The [`ControlEvent`] the action produces for the current state of the player.
+    /// returns `None` when the action does not apply, e.g. [`Action::ExitFullscreen`] outside of fullscreen.
+    pub fn control_event<P: AdvancedPlayer>(
+        self,
+        player: &P,
+        fullscreen: bool,
+    ) -> Option<ControlEvent> {
+        let speed = |rate: f64| ControlEvent::Speed(rate.clamp(SPEED_RANGE.0, SPEED_RANGE.1));
+
+        Some(match self {
+            Action::PlayPause if player.is_playing() => ControlEvent::Pause,
+            Action::PlayPause => ControlEvent::Play,
+            Action::SeekForward(amount) => {
+                ControlEvent::SeekTo((player.get_position() + amount).min(player.get_duration()))
+            }
+            Action::SeekBackward(amount) => {
+                ControlEvent::SeekTo(player.get_position().saturating_sub(amount))
+            }
+            Action::VolumeUp => ControlEvent::Volume((player.get_volume() + VOLUME_STEP).min(1.0)),
+            Action::VolumeDown => {
+                ControlEvent::Volume((player.get_volume() - VOLUME_STEP).max(0.0))
+            }
+            Action::ToggleMute => ControlEvent::ToggleMute,
+            Action::ToggleLoop => ControlEvent::ToggleLoop,
+            Action::SpeedUp => speed(player.get_playback_rate() + SPEED_STEP),
+            Action::SpeedDown => speed(player.get_playback_rate() - SPEED_STEP),
+            Action::ResetSpeed => ControlEvent::Speed(1.0),
+            Action::ToggleFullscreen => ControlEvent::ToggleFullscreen,
+            Action::ExitFullscreen if fullscreen => ControlEvent::ToggleFullscreen,
+            Action::ExitFullscreen => return None,
+        })
+    }
+}
+
+/// A key together with the modifiers that have to be held.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    /// the key
+    pub key: Key,
+    /// the modifiers held with the key, they have to match exactly
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    /// Returns a new [`Binding`] for a character key, e.g. `"m"`.
+    /// characters are matched ignoring case.
+    pub fn character(character: &str, modifiers: Modifiers) -> Self {
+        Self {
+            key: Key::Character(character.into()),
+            modifiers,
+        }
+    }
+
+    /// Returns a new [`Binding`] for a named key, e.g. [`Named::Space`].
+    pub fn named(named: Named, modifiers: Modifiers) -> Self {
+        Self {
+            key: Key::Named(named),
+            modifiers,
+        }
+    }
+
+    /// Returns true if the key press matches the [`Binding`].
+    pub fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+        let same_key = match (self.key.as_ref(), key.as_ref()) {
+            (Key::Character(bound), Key::Character(pressed)) => bound.eq_ignore_ascii_case(pressed),
+            (bound, pressed) => bound == pressed,
+        };
+
+        same_key && self.modifiers == modifiers
+    }
+}
+
+/// The keys bound to each [`Action`].
+/// the default bindings follow VLC, the same as the playerust example player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMap {
+    bindings: Vec<(Binding, Action)>,
+}
+
+impl KeyMap {
+    /// Returns a [`KeyMap`] without any bindings.
+    pub fn empty() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Binds the key to the action, replacing what the key was bound to before.
+    pub fn bind(mut self, binding: Binding, action: Action) -> Self {
+        self = self.unbind(&binding);
+        self.bindings.push((binding, action));
+        self
+    }
+
+    /// Removes the binding of the key.
+    pub fn unbind(mut self, binding: &Binding) -> Self {
+        self.bindings
+            .retain(|(bound, _)| !bound.matches(&binding.key, binding.modifiers));
+        self
+    }
+
+    /// The action bound to the key press, if any.
+    pub fn action(&self, key: &Key, modifiers: Modifiers) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.matches(key, modifiers))
+            .map(|(_, action)| *action)
+    }
+
+    /// All bindings, in the order they were bound.
+    pub fn bindings(&self) -> impl Iterator<Item = &(Binding, Action)> {
+        self.bindings.iter()
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let none = Modifiers::empty();
+        let seek = Duration::from_secs;
+
+        Self::empty()
+            .bind(Binding::named(Named::Space, none), Action::PlayPause)
+            .bind(Binding::character("l", none), Action::ToggleLoop)
+            .bind(Binding::character("[", none), Action::SpeedDown)
+            .bind(Binding::character("]", none), Action::SpeedUp)
+            .bind(Binding::character("=", none), Action::ResetSpeed)
+            .bind(
+                Binding::named(Named::ArrowRight, none),
+                Action::SeekForward(seek(1)),
+            )
+            .bind(
+                Binding::named(Named::ArrowRight, Modifiers::SHIFT),
+                Action::SeekForward(seek(3)),
+            )
+            .bind(
+                Binding::named(Named::ArrowRight, Modifiers::ALT),
+                Action::SeekForward(seek(10)),
+            )
+            .bind(
+                Binding::named(Named::ArrowRight, Modifiers::CTRL),
+                Action::SeekForward(seek(60)),
+            )
+            .bind(
+                Binding::named(Named::ArrowLeft, none),
+                Action::SeekBackward(seek(1)),
+            )
+            .bind(
+                Binding::named(Named::ArrowLeft, Modifiers::SHIFT),
+                Action::SeekBackward(seek(3)),
+            )
+            .bind(
+                Binding::named(Named::ArrowLeft, Modifiers::ALT),
+                Action::SeekBackward(seek(10)),
+            )
+            .bind(
+                Binding::named(Named::ArrowLeft, Modifiers::CTRL),
+                Action::SeekBackward(seek(60)),
+            )
+            .bind(Binding::named(Named::ArrowUp, none), Action::VolumeUp)
+            .bind(Binding::named(Named::ArrowDown, none), Action::VolumeDown)
+            .bind(Binding::character("m", none), Action::ToggleMute)
+            .bind(Binding::character("f", none), Action::ToggleFullscreen)
+            .bind(Binding::named(Named::F11, none), Action::ToggleFullscreen)
+            .bind(Binding::named(Named::Escape, none), Action::ExitFullscreen)
+    }
+}
+
+/// A widget that turns key presses into messages using a [`KeyMap`] while it is focused.
+/// it is focused by clicking it or with the focus operations of iced.
+#[allow(missing_debug_implementations)]
+pub struct KeyHandler<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    key_map: KeyMap,
+    on_action: Box<dyn Fn(Action) -> Option<Message> + 'a>,
+    id: Option<widget::Id>,
+}
+
+impl<'a, Message, Theme, Renderer> KeyHandler<'a, Message, Theme, Renderer> {
+    /// Returns a new [`KeyHandler`] around the content.
+    /// `on_action` is called with the action of every bound key pressed while focused,
+    /// a key press is left to the rest of the application when it returns `None`.
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        key_map: KeyMap,
+        on_action: impl Fn(Action) -> Option<Message> + 'a,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            key_map,
+            on_action: Box::new(on_action),
+            id: None,
+        }
+    }
+
+    /// Sets the [`widget::Id`] of the [`KeyHandler`], used to focus it.
+    pub fn id(mut self, id: widget::Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+}
+
+/// the focus of a [`KeyHandler`]
+#[derive(Debug, Default)]
+struct State {
+    is_focused: bool,
+}
+
+impl State {
+    /// a press inside the bounds focuses the handler, a press outside of them unfocuses it.
+    /// the cursor is unavailable over overlays, e.g. the controls drawn over the video,
+    /// a press there is not known to be outside and keeps the focus
+    fn press(&mut self, event: &Event, cursor: mouse::Cursor, bounds: Rectangle) {
+        let position = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => cursor.position(),
+            Event::Touch(touch::Event::FingerPressed { position, .. }) => Some(*position),
+            _ => None,
+        };
+
+        if let Some(position) = position {
+            self.is_focused = bounds.contains(position);
+        }
+    }
+}
+
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.is_focused = false;
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for KeyHandler<'a, Message, Theme, Renderer>
+where
+    Renderer: iced::advanced::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content]);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let status = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+
+        let state = tree.state.downcast_mut::<State>();
+        state.press(&event, cursor_position, layout.bounds());
+
+        match event {
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+                if state.is_focused && status == event::Status::Ignored =>
+            {
+                if let Some(message) = self
+                    .key_map
+                    .action(&key, modifiers)
+                    .and_then(|action| (self.on_action)(action))
+                {
+                    shell.publish(message);
+                    return event::Status::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        status
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        operation.focusable(tree.state.downcast_mut::<State>(), self.id.as_ref());
+        operation.container(self.id.as_ref(), layout.bounds(), &mut |operation| {
+            self.content
+                .as_widget()
+                .operate(&mut tree.children[0], layout, renderer, operation);
+        });
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<KeyHandler<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + iced::advanced::Renderer,
+    Theme: 'a,
+    Message: 'a,
+{
+    fn from(key_handler: KeyHandler<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(key_handler)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Point;
+
+    use super::*;
+
+    fn character(character: &str) -> Key {
+        Key::Character(character.into())
+    }
+
+    #[test]
+    fn default_bindings() {
+        let key_map = KeyMap::default();
+        let none = Modifiers::empty();
+        let seek = Duration::from_secs;
+
+        assert_eq!(
+            key_map.action(&Key::Named(Named::Space), none),
+            Some(Action::PlayPause)
+        );
+        assert_eq!(
+            key_map.action(&Key::Named(Named::ArrowRight), none),
+            Some(Action::SeekForward(seek(1)))
+        );
+        assert_eq!(
+            key_map.action(&Key::Named(Named::ArrowRight), Modifiers::SHIFT),
+            Some(Action::SeekForward(seek(3)))
+        );
+        assert_eq!(
+            key_map.action(&Key::Named(Named::ArrowLeft), Modifiers::CTRL),
+            Some(Action::SeekBackward(seek(60)))
+        );
+        assert_eq!(
+            key_map.action(&character("m"), none),
+            Some(Action::ToggleMute)
+        );
+        assert_eq!(
+            key_map.action(&Key::Named(Named::Escape), none),
+            Some(Action::ExitFullscreen)
+        );
+        assert_eq!(key_map.action(&character("x"), none), None);
+    }
+
+    #[test]
+    fn modifiers_have_to_match_exactly() {
+        let key_map = KeyMap::default();
+
+        assert_eq!(
+            key_map.action(
+                &Key::Named(Named::ArrowRight),
+                Modifiers::SHIFT | Modifiers::CTRL
+            ),
+            None
+        );
+        assert_eq!(key_map.action(&character("m"), Modifiers::CTRL), None);
+    }
+
+    #[test]
+    fn characters_ignore_case() {
+        let binding = Binding::character("m", Modifiers::empty());
+
+        assert!(binding.matches(&character("M"), Modifiers::empty()));
+        assert!(binding.matches(&character("m"), Modifiers::empty()));
+        assert!(!binding.matches(&character("n"), Modifiers::empty()));
+        assert!(!binding.matches(&Key::Named(Named::Space), Modifiers::empty()));
+        assert!(!Binding::named(Named::Space, Modifiers::empty())
+            .matches(&character(" "), Modifiers::empty()));
+    }
+
+    #[test]
+    fn bind_replaces_and_unbind_removes() {
+        let mute = Binding::character("m", Modifiers::empty());
+        let key_map = KeyMap::default().bind(mute.clone(), Action::ToggleLoop);
+
+        assert_eq!(
+            key_map.action(&character("m"), Modifiers::empty()),
+            Some(Action::ToggleLoop)
+        );
+        assert_eq!(
+            key_map
+                .bindings()
+                .filter(|(binding, _)| *binding == mute)
+                .count(),
+            1
+        );
+
+        let key_map = key_map.unbind(&Binding::character("M", Modifiers::empty()));
+        assert_eq!(key_map.action(&character("m"), Modifiers::empty()), None);
+        assert_eq!(
+            key_map.bindings().count(),
+            KeyMap::default().bindings().count() - 1
+        );
+    }
+
+    #[test]
+    fn presses_focus_and_unfocus() {
+        let bounds = Rectangle::new(Point::ORIGIN, Size::new(100.0, 100.0));
+        let press = Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left));
+        let mut state = State::default();
+
+        state.press(
+            &press,
+            mouse::Cursor::Available(Point::new(50.0, 50.0)),
+            bounds,
+        );
+        assert!(state.is_focused);
+
+        // over the controls overlay the cursor is unavailable, the focus is kept
+        state.press(&press, mouse::Cursor::Unavailable, bounds);
+        assert!(state.is_focused);
+
+        state.press(
+            &press,
+            mouse::Cursor::Available(Point::new(150.0, 50.0)),
+            bounds,
+        );
+        assert!(!state.is_focused);
+
+        state.press(&press, mouse::Cursor::Unavailable, bounds);
+        assert!(!state.is_focused);
+
+        let touch = Event::Touch(touch::Event::FingerPressed {
+            id: touch::Finger(0),
+            position: Point::new(10.0, 10.0),
+        });
+        state.press(&touch, mouse::Cursor::Unavailable, bounds);
+        assert!(state.is_focused);
+
+        // other events leave the focus alone
+        state.press(
+            &Event::Mouse(mouse::Event::CursorLeft),
+            mouse::Cursor::Available(Point::new(150.0, 50.0)),
+            bounds,
+        );
+        assert!(state.is_focused);
+    }
+}
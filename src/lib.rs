@@ -6,8 +6,11 @@
 //!
 //! ## Features
 //! - [x] Play videos from local files and streams
-//! - [] Play videos in fullscreen
+//! - [x] Play videos in fullscreen
 //! - [x] has a overlay for video controls
+//! - [x] seek bar preview thumbnails
+//! - [x] playlists with shuffle and repeat
+//! - [x] yuv frames converted on the gpu with [`video_shader::VideoShader`]
 #![deny(
     missing_debug_implementations,
     missing_docs,
@@ -26,8 +29,17 @@
 mod backends;
 
 pub mod helpers;
+pub mod key_map;
 pub mod overlay;
 pub mod player_handler;
+pub mod playlist;
+pub mod seek_bar;
+pub mod seek_preview;
+pub mod storyboard;
+pub mod style;
+pub mod time_display;
+pub mod video_frame;
+pub mod video_shader;
 pub mod viewer;
 
 pub use playbin_core::*;
@@ -50,4 +62,12 @@ pub type Player = gstreamer_playbin::Player;
 
 /// Default player type
 #[cfg(all(feature = "ffmpeg", not(feature = "gstreamer")))]
-pub type Player = ffmpeg_playbin::player::Player;
\ No newline at end of file
+pub type Player = ffmpeg_playbin::player::Player;
+
+/// The name of the backend used by the default [`Player`].
+#[cfg(feature = "gstreamer")]
+pub const BACKEND: &str = "gstreamer";
+
+/// The name of the backend used by the default [`Player`].
+#[cfg(all(feature = "ffmpeg", not(feature = "gstreamer")))]
+pub const BACKEND: &str = "ffmpeg";
\ No newline at end of file
@@ -1,39 +1,291 @@
 //! A widget that overlays another widget with a modal.
 //! this is used to overlay the video controls on top of the video.
+//!
+//! the modal is placed along the bottom of the base widget and can be set to
+//! hide itself after the mouse has been idle for a while.
+//! input over the modal goes to the modal, input over the rest of the base is
+//! turned into [`Gesture`]s.
+
+use std::time::Duration;
 
 use iced::{
     advanced::{
         layout, overlay, renderer,
-        widget::{self, Tree},
+        widget::{self, tree, Tree},
         Clipboard, Layout, Shell, Widget,
-    }, border::Radius, event, mouse, Alignment, Color, Element, Event, Length, Point, Rectangle, Size, Vector
+    },
+    border::Radius,
+    event, mouse,
+    time::Instant,
+    touch, window, Color, Element, Event, Length, Point, Rectangle, Size, Vector,
 };
 
+use crate::style::StyleSheet;
+
+/// How long the modal takes to fade in or out.
+const FADE_DURATION: Duration = Duration::from_millis(250);
+
+/// The longest time between two clicks that still counts as a double click.
+const DOUBLE_CLICK_DURATION: Duration = Duration::from_millis(300);
+
+/// How far the cursor has to move while pressed before it counts as a drag.
+const DRAG_THRESHOLD: f32 = 5.0;
+
+/// The cursor shown over the base while the mouse is idle.
+/// iced 0.12 has no hidden cursor, with iced 0.13 this becomes `Interaction::Hidden`.
+const IDLE_INTERACTION: mouse::Interaction = mouse::Interaction::Idle;
+
+/// A mouse gesture made over the base outside of the modal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A single click, reported once it can no longer become a double click.
+    Click,
+    /// Two clicks in quick succession.
+    DoubleClick,
+    /// The mouse wheel was scrolled by the given number of lines, positive is up.
+    Scroll(f32),
+    /// The cursor was dragged by the given offset from where it was pressed.
+    Drag(Vector),
+    /// A drag was released.
+    DragReleased,
+}
+
 /// A widget that overlays another widget with a modal.
 #[allow(missing_debug_implementations)]
-pub struct Overlay<'a, Message,Theme, Renderer> {
-    base: Element<'a, Message,Theme, Renderer>,
-    modal: Element<'a, Message,Theme, Renderer>,
+pub struct Overlay<'a, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    base: Element<'a, Message, Theme, Renderer>,
+    modal: Element<'a, Message, Theme, Renderer>,
+    idle_timeout: Option<Duration>,
+    keep_visible: bool,
+    on_gesture: Option<Box<dyn Fn(Gesture) -> Message + 'a>>,
+    on_idle: Option<Box<dyn Fn(bool) -> Message + 'a>>,
+    style: Theme::Style,
 }
 
-impl<'a, Message,Theme, Renderer> Overlay<'a, Message,Theme, Renderer> {
+impl<'a, Message, Theme, Renderer> Overlay<'a, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
     /// Returns a new [`Modal`]
     pub fn new(
-        base: impl Into<Element<'a, Message,Theme, Renderer>>,
-        modal: impl Into<Element<'a, Message,Theme, Renderer>>,
+        base: impl Into<Element<'a, Message, Theme, Renderer>>,
+        modal: impl Into<Element<'a, Message, Theme, Renderer>>,
     ) -> Self {
         Self {
             base: base.into(),
             modal: modal.into(),
+            idle_timeout: None,
+            keep_visible: false,
+            on_gesture: None,
+            on_idle: None,
+            style: Default::default(),
+        }
+    }
+
+    /// Sets the style the background of the modal is drawn with.
+    pub fn style(mut self, style: impl Into<Theme::Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// Hide the modal once the mouse has been idle over the base for the given time,
+    /// the cursor over the base is then hidden as well.
+    /// `None` keeps the modal visible at all times.
+    pub fn idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Keep the modal visible even when the mouse is idle, e.g. while the video is paused.
+    pub fn keep_visible(mut self, keep_visible: bool) -> Self {
+        self.keep_visible = keep_visible;
+        self
+    }
+
+    /// Sets the message produced for [`Gesture`]s made over the base outside of the modal.
+    pub fn on_gesture(mut self, on_gesture: impl Fn(Gesture) -> Message + 'a) -> Self {
+        self.on_gesture = Some(Box::new(on_gesture));
+        self
+    }
+
+    /// Sets the message produced when the modal was hidden because the mouse is idle (`true`)
+    /// and when the mouse is used again (`false`).
+    pub fn on_idle(mut self, on_idle: impl Fn(bool) -> Message + 'a) -> Self {
+        self.on_idle = Some(Box::new(on_idle));
+        self
+    }
+}
+
+/// tracks the mouse activity used to show and hide the modal
+/// and the gestures made over the base
+#[derive(Debug)]
+struct State {
+    last_activity: Instant,
+    /// the last time the mouse moved anywhere in the window
+    last_moved: Instant,
+    shown_at: Option<Instant>,
+    modal_hovered: bool,
+    opacity: f32,
+    pressed_at: Option<Point>,
+    dragging: bool,
+    pending_click: Option<Instant>,
+    /// the modal was hidden because the mouse is idle, reported through `on_idle`
+    idle: bool,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            last_activity: Instant::now(),
+            last_moved: Instant::now(),
+            shown_at: None,
+            modal_hovered: false,
+            opacity: 1.0,
+            pressed_at: None,
+            dragging: false,
+            pending_click: None,
+            idle: false,
+        }
+    }
+
+    /// turns mouse input over the base into a gesture
+    fn gesture(
+        &mut self,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<Gesture> {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let position = cursor.position_over(bounds)?;
+                match self.pending_click.take() {
+                    Some(deadline) if Instant::now() < deadline => {
+                        self.pressed_at = None;
+                        Some(Gesture::DoubleClick)
+                    }
+                    pending_click => {
+                        self.pressed_at = Some(position);
+                        self.dragging = false;
+                        pending_click.map(|_| Gesture::Click)
+                    }
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let pressed_at = self.pressed_at?;
+                let offset = *position - pressed_at;
+                if self.dragging || offset.x.hypot(offset.y) > DRAG_THRESHOLD {
+                    self.dragging = true;
+                    Some(Gesture::Drag(offset))
+                } else {
+                    None
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                let _ = self.pressed_at.take()?;
+                if self.dragging {
+                    self.dragging = false;
+                    Some(Gesture::DragReleased)
+                } else {
+                    self.pending_click = Some(Instant::now() + DOUBLE_CLICK_DURATION);
+                    None
+                }
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let _ = cursor.position_over(bounds)?;
+                match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => Some(Gesture::Scroll(*y)),
+                    mouse::ScrollDelta::Pixels { y, .. } => Some(Gesture::Scroll(y / 20.0)),
+                }
+            }
+            Event::Window(_, window::Event::RedrawRequested(now)) => {
+                let deadline = self.pending_click?;
+                if *now >= deadline {
+                    self.pending_click = None;
+                    Some(Gesture::Click)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// the mouse moved or was used, show the modal again
+    fn activity(&mut self, now: Instant) {
+        if self.opacity == 0.0 {
+            self.shown_at = Some(now);
+        }
+        self.last_activity = now;
+    }
+
+    /// the modal faded out and the mouse has not moved anywhere for the idle timeout
+    fn is_idle(&self, now: Instant, idle_timeout: Option<Duration>) -> bool {
+        idle_timeout.is_some_and(|idle_timeout| {
+            self.opacity == 0.0
+                && self.shown_at.is_none()
+                && now.saturating_duration_since(self.last_moved) >= idle_timeout
+        })
+    }
+
+    /// updates the opacity and returns when the modal needs to be redrawn next
+    fn update(
+        &mut self,
+        now: Instant,
+        idle_timeout: Option<Duration>,
+        keep_visible: bool,
+    ) -> Option<window::RedrawRequest> {
+        let Some(idle_timeout) = idle_timeout else {
+            self.opacity = 1.0;
+            return None;
+        };
+
+        if keep_visible || self.modal_hovered {
+            self.last_activity = now;
+        }
+
+        let fade_in = self.shown_at.map_or(1.0, |shown_at| {
+            (now.saturating_duration_since(shown_at).as_secs_f32() / FADE_DURATION.as_secs_f32())
+                .min(1.0)
+        });
+        if fade_in >= 1.0 {
+            self.shown_at = None;
+        }
+
+        let idle = now.saturating_duration_since(self.last_activity);
+        let fade_out = 1.0
+            - (idle.saturating_sub(idle_timeout).as_secs_f32() / FADE_DURATION.as_secs_f32())
+                .min(1.0);
+
+        self.opacity = fade_in.min(fade_out);
+
+        if self.opacity == 0.0 {
+            None
+        } else if fade_in < 1.0 || idle >= idle_timeout {
+            Some(window::RedrawRequest::NextFrame)
+        } else {
+            Some(window::RedrawRequest::At(self.last_activity + idle_timeout))
         }
     }
 }
 
-impl<'a, Message,Theme, Renderer> Widget<Message,Theme, Renderer> for Overlay<'a, Message,Theme, Renderer>
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Overlay<'a, Message, Theme, Renderer>
 where
     Renderer: iced::advanced::Renderer,
+    Theme: StyleSheet,
     Message: Clone,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::new())
+    }
+
     fn children(&self) -> Vec<Tree> {
         vec![Tree::new(&self.base), Tree::new(&self.modal)]
     }
@@ -46,10 +298,15 @@ where
         self.base.as_widget().size()
     }
 
-    fn layout(&self, tree: &mut Tree,
+    fn layout(
+        &self,
+        tree: &mut Tree,
         renderer: &Renderer,
-        limits: &layout::Limits,) -> layout::Node {
-        self.base.as_widget().layout(tree, renderer, limits)
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.base
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
     }
 
     fn on_event(
@@ -63,16 +320,95 @@ where
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle,
     ) -> event::Status {
-        self.base.as_widget_mut().on_event(
+        let overlay_state = state.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+
+        let active = match &event {
+            Event::Mouse(mouse::Event::CursorMoved { position })
+            | Event::Touch(touch::Event::FingerPressed { position, .. })
+            | Event::Touch(touch::Event::FingerMoved { position, .. }) => {
+                bounds.contains(*position)
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+            | Event::Mouse(mouse::Event::WheelScrolled { .. }) => {
+                overlay_state.modal_hovered || cursor_position.is_over(bounds)
+            }
+            _ => false,
+        };
+
+        let now = match event {
+            Event::Window(_, window::Event::RedrawRequested(now)) => now,
+            _ => Instant::now(),
+        };
+
+        if active {
+            overlay_state.activity(now);
+            shell.request_redraw(window::RedrawRequest::NextFrame);
+        }
+
+        // moving the mouse anywhere ends idle, also over the widgets around the base
+        if active
+            || matches!(
+                event,
+                Event::Mouse(mouse::Event::CursorMoved { .. } | mouse::Event::CursorLeft)
+            )
+        {
+            overlay_state.last_moved = now;
+        }
+
+        if let Event::Window(_, window::Event::RedrawRequested(now)) = event {
+            if let Some(redraw) = overlay_state.update(now, self.idle_timeout, self.keep_visible) {
+                shell.request_redraw(redraw);
+            }
+        }
+
+        let idle = overlay_state.is_idle(now, self.idle_timeout);
+        if let Some(idle_timeout) = self.idle_timeout.filter(|_| !idle) {
+            if overlay_state.opacity == 0.0 {
+                // the mouse moved outside of the base while the modal stayed hidden
+                shell.request_redraw(window::RedrawRequest::At(
+                    overlay_state.last_moved + idle_timeout,
+                ));
+            }
+        }
+        if idle != overlay_state.idle {
+            overlay_state.idle = idle;
+            if let Some(on_idle) = &self.on_idle {
+                shell.publish(on_idle(idle));
+            }
+        }
+
+        if let Some(on_gesture) = &self.on_gesture {
+            if let Some(gesture) = overlay_state.gesture(&event, bounds, cursor_position) {
+                shell.publish(on_gesture(gesture));
+            }
+
+            if let Some(deadline) = overlay_state.pending_click {
+                shell.request_redraw(window::RedrawRequest::At(deadline));
+            }
+        }
+
+        let status = self.base.as_widget_mut().on_event(
             &mut state.children[0],
-            event,
+            event.clone(),
             layout,
             cursor_position,
             renderer,
             clipboard,
             shell,
             viewport,
-        )
+        );
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+            | Event::Mouse(mouse::Event::ButtonReleased(_))
+            | Event::Mouse(mouse::Event::WheelScrolled { .. })
+                if self.on_gesture.is_some() && cursor_position.is_over(bounds) =>
+            {
+                event::Status::Captured
+            }
+            _ => status,
+        }
     }
 
     fn draw(
@@ -100,17 +436,27 @@ where
         &'b mut self,
         state: &'b mut Tree,
         layout: Layout<'_>,
-       _renderer: &Renderer,
+        _renderer: &Renderer,
         translation: Vector,
-    ) -> Option<overlay::Element<'b, Message,Theme, Renderer>> {
-        Some(overlay::Element::new(
-            Box::new(OverlayInternal {
-                position: layout.bounds().position() + translation,
-                content: &mut self.modal,
-                tree: &mut state.children[1],
-                size: layout.bounds().size(),
-            }),
-        ))
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let Tree {
+            state, children, ..
+        } = state;
+        let state = state.downcast_mut::<State>();
+
+        if state.opacity == 0.0 {
+            state.modal_hovered = false;
+            return None;
+        }
+
+        Some(overlay::Element::new(Box::new(OverlayInternal {
+            position: layout.bounds().position() + translation,
+            content: &mut self.modal,
+            tree: &mut children[1],
+            state,
+            style: &self.style,
+            size: layout.bounds().size(),
+        })))
     }
 
     fn mouse_interaction(
@@ -121,6 +467,10 @@ where
         viewport: &Rectangle,
         renderer: &Renderer,
     ) -> mouse::Interaction {
+        if state.state.downcast_ref::<State>().idle && cursor_position.is_over(layout.bounds()) {
+            return IDLE_INTERACTION;
+        }
+
         self.base.as_widget().mouse_interaction(
             &state.children[0],
             layout,
@@ -143,29 +493,43 @@ where
     }
 }
 
-struct OverlayInternal<'a, 'b, Message,Theme, Renderer> {
-    content: &'b mut Element<'a, Message,Theme, Renderer>,
+struct OverlayInternal<'a, 'b, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    content: &'b mut Element<'a, Message, Theme, Renderer>,
     tree: &'b mut Tree,
+    state: &'b mut State,
+    style: &'b Theme::Style,
     size: Size,
     position: Point,
 }
 
-impl<'a, 'b, Message,Theme, Renderer> overlay::Overlay<Message,Theme, Renderer>
-    for OverlayInternal<'a, 'b, Message,Theme, Renderer>
+impl<'a, 'b, Message, Theme, Renderer> overlay::Overlay<Message, Theme, Renderer>
+    for OverlayInternal<'a, 'b, Message, Theme, Renderer>
 where
     Renderer: iced::advanced::Renderer,
+    Theme: StyleSheet,
     Message: Clone,
 {
     fn layout(&mut self, renderer: &Renderer, _bounds: Size) -> layout::Node {
-        let limits = layout::Limits::new(Size::ZERO, self.size)
-            .width(Length::Fill)
-            .height(Length::Fill);
+        let limits = layout::Limits::new(Size::ZERO, self.size).width(Length::Fill);
 
-        let child = self.content.as_widget().layout(&mut self.tree, renderer, &limits).align(Alignment::Center, Alignment::Center, limits.max());
+        let child = self
+            .content
+            .as_widget()
+            .layout(self.tree, renderer, &limits);
+        let child_height = child.size().height;
+        let child = child.move_to(Point::new(0.0, self.size.height - child_height));
 
-         layout::Node::with_children(self.size, vec![child]).move_to(self.position)
+        layout::Node::with_children(self.size, vec![child]).move_to(self.position)
+    }
 
-  
+    fn is_over(&self, layout: Layout<'_>, _renderer: &Renderer, cursor_position: Point) -> bool {
+        layout
+            .children()
+            .next()
+            .is_some_and(|content| content.bounds().contains(cursor_position))
     }
 
     fn on_event(
@@ -176,16 +540,18 @@ where
         renderer: &Renderer,
         clipboard: &mut dyn Clipboard,
         shell: &mut Shell<'_, Message>,
-
     ) -> event::Status {
-        let _content_bounds = layout.children().next().unwrap().bounds();
-
+        let content_layout = layout.children().next().unwrap();
         let viewport = layout.bounds();
 
+        // the modal is only an overlay while it is at least partly visible, it takes input
+        // while it fades as `is_over` tells iced the cursor is over it
+        self.state.modal_hovered = cursor_position.is_over(content_layout.bounds());
+
         self.content.as_widget_mut().on_event(
             self.tree,
             event,
-            layout.children().next().unwrap(),
+            content_layout,
             cursor_position,
             renderer,
             clipboard,
@@ -202,31 +568,61 @@ where
         layout: Layout<'_>,
         cursor_position: iced::mouse::Cursor,
     ) {
-        renderer.fill_quad(
-            renderer::Quad {
-                bounds: layout.bounds(),
-                border: iced::Border { 
-                    radius: Radius::from(0.0),
-                    width: 0.0,
-                    color: Color::TRANSPARENT,
-                },
-                shadow: iced::Shadow::default(),
-            },
-            Color {
-                a: 0.80,
-                ..Color::BLACK
-            },
-        );
+        let content_layout = layout.children().next().unwrap();
+        let content_bounds = content_layout.bounds();
+        let opacity = self.state.opacity;
+        let background = theme.appearance(self.style).background;
+
+        // slide the modal out of view as it fades
+        let offset = Vector::new(0.0, content_bounds.height * (1.0 - opacity));
 
-        self.content.as_widget().draw(
+        renderer.with_layer(layout.bounds(), |renderer| {
+            renderer.with_translation(offset, |renderer| {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: content_bounds,
+                        border: iced::Border {
+                            radius: Radius::from(0.0),
+                            width: 0.0,
+                            color: Color::TRANSPARENT,
+                        },
+                        shadow: iced::Shadow::default(),
+                    },
+                    Color {
+                        a: background.a * opacity,
+                        ..background
+                    },
+                );
+
+                self.content.as_widget().draw(
+                    self.tree,
+                    renderer,
+                    theme,
+                    &renderer::Style {
+                        text_color: Color {
+                            a: style.text_color.a * opacity,
+                            ..style.text_color
+                        },
+                    },
+                    content_layout,
+                    cursor_position,
+                    &layout.bounds(),
+                );
+            });
+        });
+    }
+
+    fn overlay<'c>(
+        &'c mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'c, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
             self.tree,
-            renderer,
-            theme,
-            style,
             layout.children().next().unwrap(),
-            cursor_position,
-            &layout.bounds(),
-        );
+            renderer,
+            Vector::ZERO,
+        )
     }
 
     fn operate(
@@ -260,13 +656,14 @@ where
     }
 }
 
-impl<'a, Message,Theme, Renderer> From<Overlay<'a,Message,Theme, Renderer>> for Element<'a, Message,Theme, Renderer>
+impl<'a, Message, Theme, Renderer> From<Overlay<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
 where
     Renderer: 'a + iced::advanced::Renderer,
-    Theme: 'a ,
+    Theme: 'a + StyleSheet,
     Message: 'a + Clone,
 {
-    fn from(modal: Overlay<'a, Message,Theme, Renderer>) -> Self {
+    fn from(modal: Overlay<'a, Message, Theme, Renderer>) -> Self {
         Element::new(modal)
     }
 }
@@ -2,24 +2,45 @@
 //! offers a high level api to interact with the players
 
 use iced::widget::image;
-use playbin_core::{BasicPlayer, PlayerBuilder, PlayerMessage};
+use playbin_core::{
+    BasicPlayer, DecodedFrame, FrameCounters, MediaInfo, PlayerBuilder, PlayerMessage, ProbeBuilder, ProbePlayer,
+    StoryboardBuilder, StoryboardPlayer,
+};
 
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::{
+    playlist::{Playlist, PlaylistEvent},
+    storyboard::Storyboard,
+};
+
+/// the most videos probed at the same time
+const MAX_PROBES: usize = 4;
 
 /// A struct that handles all the players and images
 #[derive(Debug)]
 pub struct PlayerHandler<P> {
     subscriptions: Vec<PlayerBuilder>,
+    storyboard_subscriptions: Vec<StoryboardBuilder>,
+    probe_subscriptions: Vec<ProbeBuilder>,
     players: HashMap<String, P>,
-    images: HashMap<String, image::Handle>,
+    images: HashMap<String, DecodedFrame>,
+    storyboards: HashMap<String, Storyboard>,
+    playlists: HashMap<String, Playlist>,
+    media_info: HashMap<String, MediaInfo>,
 }
 
 impl<P> Default for PlayerHandler<P> {
     fn default() -> Self {
         Self {
             subscriptions: Vec::new(),
+            storyboard_subscriptions: Vec::new(),
+            probe_subscriptions: Vec::new(),
             players: HashMap::new(),
             images: HashMap::new(),
+            storyboards: HashMap::new(),
+            playlists: HashMap::new(),
+            media_info: HashMap::new(),
         }
     }
 }
@@ -39,8 +60,9 @@ impl<P: BasicPlayer + std::marker::Send + 'static> PlayerHandler<P> {
         iced::Subscription::batch(subscriptions)
     }
 
-    /// handle the messages from the subscriptions
-    pub fn handle_event(&mut self, message: PlayerMessage<P>) {
+    /// handle the messages from the subscriptions,
+    /// returns an event when a playlist moved on at the end of a video
+    pub fn handle_event(&mut self, message: PlayerMessage<P>) -> Option<PlaylistEvent> {
         match message {
             PlayerMessage::Player(id, player) => {
                 let _ = self.players.insert(id, player);
@@ -48,8 +70,125 @@ impl<P: BasicPlayer + std::marker::Send + 'static> PlayerHandler<P> {
             PlayerMessage::Frame(id, image) => {
                 let _ = self.images.insert(id, image);
             }
+            PlayerMessage::Thumbnail(id, time, image) => {
+                self.storyboards.entry(id).or_default().insert(time, image);
+            }
+            PlayerMessage::Probed(id, info) => {
+                self.probe_subscriptions.retain(|probe| probe.id != id);
+                let _ = self.media_info.insert(id, info);
+            }
+            PlayerMessage::EndOfStream(id) => {
+                let index = self.playlists.get_mut(&id)?.advance();
+                return Some(self.play_index(id, index));
+            }
+            PlayerMessage::NextSource(id, uri) => {
+                let playlist = self.playlists.get_mut(&id)?;
+                let index = playlist.advance();
+                let item = index.and_then(|index| playlist.get(index));
+
+                // the playlist changed after the source was queued, play what it has now
+                if item.map(|item| item.uri.as_str()) != Some(uri.as_str()) {
+                    return Some(self.play_index(id, index));
+                }
+                self.queue_next(&id);
+                return index.map(|index| PlaylistEvent::Changed { id, index, uri });
+            }
+        }
+        None
+    }
+
+    /// queue the item the playlist moves to at the end of the current video,
+    /// so backends supporting it play it without a gap.
+    /// called when playing an item, call it again after changing the playlist
+    pub fn queue_next(&mut self, id: &str) {
+        let (Some(playlist), Some(player)) = (self.playlists.get(id), self.players.get(id)) else {
+            return;
+        };
+        let next = playlist
+            .peek_advance()
+            .and_then(|index| playlist.get(index))
+            .map(|item| PathBuf::from(&item.uri));
+        player.set_next_source(next.as_ref());
+    }
+
+    /// play the next item of the playlist attached to the player
+    pub fn next(&mut self, id: &str) -> Option<PlaylistEvent> {
+        let index = self.playlists.get_mut(id)?.play_next();
+        Some(self.play_index(id.to_string(), index))
+    }
+
+    /// play the previous item of the playlist attached to the player
+    pub fn previous(&mut self, id: &str) -> Option<PlaylistEvent> {
+        let index = self.playlists.get_mut(id)?.play_previous();
+        Some(self.play_index(id.to_string(), index))
+    }
+
+    /// play the item at the index of the playlist attached to the player
+    pub fn play(&mut self, id: &str, index: usize) -> Option<PlaylistEvent> {
+        let index = self.playlists.get_mut(id)?.play(index)?;
+        Some(self.play_index(id.to_string(), Some(index)))
+    }
+
+    /// set the source of the player to the playlist item
+    fn play_index(&mut self, id: String, index: Option<usize>) -> PlaylistEvent {
+        let item = index.and_then(|index| Some((index, self.playlists.get(&id)?.get(index)?)));
+        let Some((index, item)) = item else {
+            return PlaylistEvent::Finished { id };
+        };
+
+        let uri = item.uri.clone();
+        if let Some(player) = self.players.get_mut(&id) {
+            if player.set_source(&PathBuf::from(&uri)).is_err() {
+                tracing::error!("could not play {}", uri);
+            }
+        }
+        self.queue_next(&id);
+        PlaylistEvent::Changed { id, index, uri }
+    }
+}
+
+impl<P: StoryboardPlayer + std::marker::Send + 'static> PlayerHandler<P> {
+    /// start generating preview thumbnails for a player,
+    /// replaces any storyboard previously generated for the same id
+    pub fn start_storyboard(&mut self, settings: StoryboardBuilder) {
+        self.storyboard_subscriptions
+            .retain(|storyboard| storyboard.id != settings.id);
+        let _ = self.storyboards.remove(&settings.id);
+        self.storyboard_subscriptions.push(settings);
+    }
+
+    /// the subscriptions generating the storyboards
+    pub fn storyboard_subscriptions(&self) -> iced::Subscription<PlayerMessage<P>> {
+        let subscriptions = self
+            .storyboard_subscriptions
+            .iter()
+            .map(|settings| settings.clone().build());
+        iced::Subscription::batch(subscriptions)
+    }
+}
+
+impl<P: ProbePlayer + std::marker::Send + 'static> PlayerHandler<P> {
+    /// read the duration and a thumbnail of a video in the background.
+    /// videos already probed or waiting to be are skipped
+    pub fn start_probe(&mut self, settings: ProbeBuilder) {
+        let queued = self
+            .probe_subscriptions
+            .iter()
+            .any(|probe| probe.id == settings.id);
+        if !queued && !self.media_info.contains_key(&settings.id) {
+            self.probe_subscriptions.push(settings);
         }
     }
+
+    /// the subscriptions probing videos, a few at a time
+    pub fn probe_subscriptions(&self) -> iced::Subscription<PlayerMessage<P>> {
+        let subscriptions = self
+            .probe_subscriptions
+            .iter()
+            .take(MAX_PROBES)
+            .map(|settings| settings.clone().build());
+        iced::Subscription::batch(subscriptions)
+    }
 }
 
 impl<P> PlayerHandler<P> {
@@ -68,13 +207,75 @@ impl<P> PlayerHandler<P> {
         &self.players
     }
 
-    /// get a reference to the image
+    /// get a reference to the image, yuv frames are converted to rgba on the cpu.
+    /// use [`get_decoded_frame`](Self::get_decoded_frame) to show them without converting them
     pub fn get_frame(&self, id: &str) -> Option<&image::Handle> {
+        self.images.get(id).map(DecodedFrame::rgba)
+    }
+
+    /// get the frame in the format the player sent it in, shown with
+    /// [`VideoShader`](crate::video_shader::VideoShader), on its own or in a
+    /// [`VideoView`](crate::viewer::VideoView) with [`shader`](crate::viewer::VideoView::shader)
+    pub fn get_decoded_frame(&self, id: &str) -> Option<&DecodedFrame> {
         self.images.get(id)
     }
 
-    /// get all the images in a hashmap
-    pub fn get_all_images(&self) -> &HashMap<String, image::Handle> {
+    /// how many frames a player sent and how many were dropped because the ui fell behind
+    pub fn get_frame_counters(&self, id: &str) -> Option<&FrameCounters> {
+        self.subscriptions
+            .iter()
+            .find(|settings| settings.id == id)
+            .map(PlayerBuilder::frame_counters)
+    }
+
+    /// get the preview thumbnails of a player
+    pub fn get_storyboard(&self, id: &str) -> Option<&Storyboard> {
+        self.storyboards.get(id)
+    }
+
+    /// attach a playlist to the player, replacing the one it had.
+    /// nothing is played until the playlist is moved with [`PlayerHandler::next`] or [`PlayerHandler::play`]
+    pub fn attach_playlist(&mut self, id: impl Into<String>, playlist: Playlist) {
+        let _ = self.playlists.insert(id.into(), playlist);
+    }
+
+    /// remove the playlist from the player, it stops at the end of the current video
+    pub fn detach_playlist(&mut self, id: &str) -> Option<Playlist>
+    where
+        P: BasicPlayer,
+    {
+        if let Some(player) = self.players.get(id) {
+            player.set_next_source(None);
+        }
+        self.playlists.remove(id)
+    }
+
+    /// get the playlist attached to the player
+    pub fn get_playlist(&self, id: &str) -> Option<&Playlist> {
+        self.playlists.get(id)
+    }
+
+    /// get a mutable reference to the playlist attached to the player
+    pub fn get_playlist_mut(&mut self, id: &str) -> Option<&mut Playlist> {
+        self.playlists.get_mut(id)
+    }
+
+    /// get what probing a video found out
+    pub fn get_media_info(&self, id: &str) -> Option<&MediaInfo> {
+        self.media_info.get(id)
+    }
+
+    /// get all the images in a hashmap, yuv frames are converted to rgba on the cpu.
+    /// use [`get_all_decoded_frames`](Self::get_all_decoded_frames) to get them without converting them
+    pub fn get_all_images(&self) -> HashMap<String, image::Handle> {
+        self.images
+            .iter()
+            .map(|(id, frame)| (id.clone(), frame.rgba().clone()))
+            .collect()
+    }
+
+    /// get all the frames in a hashmap, in the format the players sent them in
+    pub fn get_all_decoded_frames(&self) -> &HashMap<String, DecodedFrame> {
         &self.images
     }
 
@@ -83,7 +284,7 @@ impl<P> PlayerHandler<P> {
     pub fn players_and_images(&self) -> Vec<(&String, &P, &image::Handle)> {
         self.players
             .iter()
-            .filter_map(|(id, player)| self.images.get(id).map(|image| (id, player, image)))
+            .filter_map(|(id, player)| self.get_frame(id).map(|image| (id, player, image)))
             .collect()
     }
 }
@@ -0,0 +1,289 @@
+//! reading and writing playlist files
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use url::Url;
+
+use super::{m3u, pls, xspf, Playlist};
+
+/// the playlist file formats that can be read and written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistFormat {
+    /// M3U and M3U8, a list of paths with optional `#EXTINF` titles and durations
+    M3u,
+    /// PLS, an ini file with `File`, `Title` and `Length` entries
+    Pls,
+    /// XSPF, the xml shareable playlist format
+    Xspf,
+}
+
+impl PlaylistFormat {
+    /// all the formats
+    pub const ALL: [PlaylistFormat; 3] = [Self::M3u, Self::Pls, Self::Xspf];
+
+    /// the format of a file by its extension
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+    }
+
+    /// the file extensions of the format, the first one is used when saving
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            PlaylistFormat::M3u => &["m3u8", "m3u"],
+            PlaylistFormat::Pls => &["pls"],
+            PlaylistFormat::Xspf => &["xspf"],
+        }
+    }
+
+    /// the name of the format
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlaylistFormat::M3u => "M3U",
+            PlaylistFormat::Pls => "PLS",
+            PlaylistFormat::Xspf => "XSPF",
+        }
+    }
+}
+
+/// why a playlist file could not be read or written
+#[derive(Debug)]
+pub enum PlaylistError {
+    /// the file could not be read or written
+    Io(io::Error),
+    /// the file is not a valid playlist
+    Parse(String),
+    /// the extension of the file is not a known playlist format
+    UnknownFormat(PathBuf),
+}
+
+impl fmt::Display for PlaylistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaylistError::Io(err) => write!(f, "{}", err),
+            PlaylistError::Parse(err) => write!(f, "invalid playlist: {}", err),
+            PlaylistError::UnknownFormat(path) => {
+                write!(f, "{} is not a known playlist format", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlaylistError {}
+
+impl From<io::Error> for PlaylistError {
+    fn from(err: io::Error) -> Self {
+        PlaylistError::Io(err)
+    }
+}
+
+impl Playlist {
+    /// read a playlist, relative paths in it are relative to the directory `base`
+    pub fn parse(
+        text: &str,
+        format: PlaylistFormat,
+        base: Option<&Path>,
+    ) -> Result<Self, PlaylistError> {
+        let text = text.trim_start_matches('\u{feff}');
+        let items = match format {
+            PlaylistFormat::M3u => m3u::parse(text, base),
+            PlaylistFormat::Pls => pls::parse(text, base)?,
+            PlaylistFormat::Xspf => xspf::parse(text, base)?,
+        };
+        Ok(items.into_iter().collect())
+    }
+
+    /// read a playlist file, the format is taken from the extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PlaylistError> {
+        let path = path.as_ref();
+        let format = PlaylistFormat::from_path(path)
+            .ok_or_else(|| PlaylistError::UnknownFormat(path.to_path_buf()))?;
+
+        // plain m3u files are often latin-1 instead of utf-8
+        let text = match String::from_utf8(fs::read(path)?) {
+            Ok(text) => text,
+            Err(err) => err.into_bytes().into_iter().map(char::from).collect(),
+        };
+        Self::parse(&text, format, path.parent())
+    }
+
+    /// write the playlist, files in the directory `base` are written relative to it
+    pub fn write(&self, format: PlaylistFormat, base: Option<&Path>) -> String {
+        match format {
+            PlaylistFormat::M3u => m3u::write(self.items(), base),
+            PlaylistFormat::Pls => pls::write(self.items(), base),
+            PlaylistFormat::Xspf => xspf::write(self.items(), base),
+        }
+    }
+
+    /// write the playlist to a file, the format is taken from the extension
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PlaylistError> {
+        let path = path.as_ref();
+        let format = PlaylistFormat::from_path(path)
+            .ok_or_else(|| PlaylistError::UnknownFormat(path.to_path_buf()))?;
+
+        fs::write(path, self.write(format, path.parent()))?;
+        Ok(())
+    }
+}
+
+/// the uri of an entry written as a path, relative paths are relative to `base`.
+/// entries that already are uris are kept as they are
+pub(super) fn path_to_uri(location: &str, base: Option<&Path>) -> String {
+    if location.contains("://") {
+        return location.to_string();
+    }
+
+    let path = match base {
+        Some(base) => base.join(location),
+        None => PathBuf::from(location),
+    };
+    Url::from_file_path(&path)
+        .map(String::from)
+        .unwrap_or_else(|_| location.to_string())
+}
+
+/// how an entry is written as a path, files in `base` are written relative to it
+pub(super) fn uri_to_path(uri: &str, base: Option<&Path>) -> String {
+    let Some(path) = Url::parse(uri)
+        .ok()
+        .filter(|url| url.scheme() == "file")
+        .and_then(|url| url.to_file_path().ok())
+    else {
+        return uri.to_string();
+    };
+
+    base.and_then(|base| path.strip_prefix(base).ok())
+        .unwrap_or(&path)
+        .display()
+        .to_string()
+}
+
+/// the uri of an entry written as a uri reference, relative ones are relative to `base`
+pub(super) fn resolve_uri(location: &str, base: Option<&Path>) -> String {
+    match Url::parse(location) {
+        Ok(url) => url.into(),
+        Err(url::ParseError::RelativeUrlWithoutBase) => base
+            .and_then(|base| Url::from_directory_path(base).ok())
+            .and_then(|base| base.join(location).ok())
+            .map_or_else(|| location.to_string(), String::from),
+        Err(_) => location.to_string(),
+    }
+}
+
+/// how an entry is written as a uri reference, files in `base` are written relative to it
+pub(super) fn relative_uri(uri: &str, base: Option<&Path>) -> String {
+    let relative = base
+        .and_then(|base| Url::from_directory_path(base).ok())
+        .zip(Url::parse(uri).ok())
+        .filter(|(base, url)| url.as_str().starts_with(base.as_str()))
+        .and_then(|(base, url)| base.make_relative(&url));
+    relative.unwrap_or_else(|| uri.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_by_extension() {
+        assert_eq!(
+            PlaylistFormat::from_path("a/list.M3U8"),
+            Some(PlaylistFormat::M3u)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path("list.m3u"),
+            Some(PlaylistFormat::M3u)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path("list.pls"),
+            Some(PlaylistFormat::Pls)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path("list.xspf"),
+            Some(PlaylistFormat::Xspf)
+        );
+        assert_eq!(PlaylistFormat::from_path("video.mp4"), None);
+        assert_eq!(PlaylistFormat::from_path("m3u"), None);
+    }
+
+    #[test]
+    fn parse_skips_the_byte_order_mark() {
+        let playlist = Playlist::parse(
+            "\u{feff}[playlist]\nFile1=http://host/a.mp4\n",
+            PlaylistFormat::Pls,
+            None,
+        )
+        .unwrap();
+        assert_eq!(playlist.len(), 1);
+    }
+
+    #[test]
+    fn uris_and_paths() {
+        let base = std::env::temp_dir().join("playlists");
+        let uri = String::from(Url::from_file_path(base.join("a.mp4")).unwrap());
+
+        assert_eq!(path_to_uri("a.mp4", Some(&base)), uri);
+        assert_eq!(
+            path_to_uri("http://host/a.mp4", Some(&base)),
+            "http://host/a.mp4"
+        );
+        assert_eq!(uri_to_path(&uri, Some(&base)), "a.mp4");
+        assert_eq!(
+            uri_to_path("http://host/a.mp4", Some(&base)),
+            "http://host/a.mp4"
+        );
+
+        assert_eq!(resolve_uri("a.mp4", Some(&base)), uri);
+        assert_eq!(relative_uri(&uri, Some(&base)), "a.mp4");
+        // files outside of the directory keep their full uri
+        let other = String::from(Url::from_file_path(std::env::temp_dir().join("b.mp4")).unwrap());
+        assert_eq!(relative_uri(&other, Some(&base)), other);
+        assert_eq!(
+            uri_to_path(&other, Some(&base)),
+            std::env::temp_dir().join("b.mp4").display().to_string()
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = std::env::temp_dir().join(format!("iced_video_playlist_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let playlist: Playlist = [
+            String::from(Url::from_file_path(dir.join("a.mp4")).unwrap()),
+            String::from("http://host/b.mp4"),
+        ]
+        .into_iter()
+        .collect();
+
+        for format in PlaylistFormat::ALL {
+            let path = dir.join(format!("list.{}", format.extensions()[0]));
+            playlist.save(&path).unwrap();
+            let loaded = Playlist::load(&path).unwrap();
+            assert_eq!(loaded.items(), playlist.items(), "{}", format.name());
+        }
+
+        // latin-1 m3u files are read as well
+        let path = dir.join("latin.m3u");
+        fs::write(&path, b"#EXTINF:1,Caf\xe9\nhttp://host/a.mp4\n").unwrap();
+        let loaded = Playlist::load(&path).unwrap();
+        assert_eq!(loaded.items()[0].title.as_deref(), Some("Café"));
+
+        assert!(matches!(
+            Playlist::load(dir.join("list.txt")),
+            Err(PlaylistError::UnknownFormat(_))
+        ));
+        assert!(matches!(
+            Playlist::load(dir.join("missing.pls")),
+            Err(PlaylistError::Io(_))
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,150 @@
+//! M3U and M3U8 playlists
+//! a path or url per line, with optional `#EXTINF:<seconds>,<title>` lines before them
+
+use std::{fmt::Write, path::Path, time::Duration};
+
+use super::{
+    format::{path_to_uri, uri_to_path},
+    PlaylistItem,
+};
+
+/// read the entries of a m3u playlist
+pub(super) fn parse(text: &str, base: Option<&Path>) -> Vec<PlaylistItem> {
+    let mut items = Vec::new();
+    let mut info = None;
+
+    for line in text.lines().map(str::trim) {
+        if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+            info = Some(parse_extinf(extinf));
+        } else if !line.is_empty() && !line.starts_with('#') {
+            let mut item = PlaylistItem::new(path_to_uri(line, base));
+            if let Some((duration, title)) = info.take() {
+                item.duration = duration;
+                item.title = title;
+            }
+            items.push(item);
+        }
+    }
+    items
+}
+
+/// the duration and title of `<seconds> <attributes>,<title>`, a negative duration is unknown
+fn parse_extinf(extinf: &str) -> (Option<Duration>, Option<String>) {
+    // the title starts after the first comma that is not in a quoted attribute
+    let mut quoted = false;
+    let comma = extinf.char_indices().find_map(|(i, c)| {
+        quoted ^= c == '"';
+        (c == ',' && !quoted).then_some(i)
+    });
+    let (info, title) = match comma {
+        Some(comma) => (&extinf[..comma], Some(extinf[comma + 1..].trim())),
+        None => (extinf, None),
+    };
+
+    let duration = info
+        .split_whitespace()
+        .next()
+        .and_then(|seconds| seconds.parse::<f64>().ok())
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .map(Duration::from_secs_f64);
+    let title = title.filter(|title| !title.is_empty()).map(String::from);
+    (duration, title)
+}
+
+/// write the items as an extended m3u playlist
+pub(super) fn write(items: &[PlaylistItem], base: Option<&Path>) -> String {
+    let mut text = String::from("#EXTM3U\n");
+    for item in items {
+        if item.title.is_some() || item.duration.is_some() {
+            let seconds = item
+                .duration
+                .map_or(-1, |duration| duration.as_secs_f64().round() as i64);
+            let _ = writeln!(text, "#EXTINF:{},{}", seconds, item.name());
+        }
+        let _ = writeln!(text, "{}", uri_to_path(&item.uri, base));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use url::Url;
+
+    use super::*;
+
+    fn base() -> PathBuf {
+        std::env::temp_dir().join("playlists")
+    }
+
+    fn file_uri(path: PathBuf) -> String {
+        Url::from_file_path(path).unwrap().into()
+    }
+
+    #[test]
+    fn parses_extended_m3u() {
+        let text = "#EXTM3U\r\n\
+                    #EXTINF:123,Artist - Title\r\n\
+                    http://host/a.mp4\r\n\
+                    \r\n\
+                    # a comment\r\n\
+                    #EXTINF:-1 tvg-name=\"a, b\" group-title=\"news\",Live\r\n\
+                    http://host/live.m3u8\r\n\
+                    http://host/plain.mp4\r\n";
+        let items = parse(text, None);
+
+        assert_eq!(
+            items,
+            vec![
+                PlaylistItem::new("http://host/a.mp4")
+                    .title("Artist - Title")
+                    .duration(Duration::from_secs(123)),
+                PlaylistItem::new("http://host/live.m3u8").title("Live"),
+                PlaylistItem::new("http://host/plain.mp4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn extinf_without_a_title() {
+        assert_eq!(
+            parse_extinf("12.5"),
+            (Some(Duration::from_millis(12_500)), None)
+        );
+        assert_eq!(parse_extinf("nan,"), (None, None));
+        assert_eq!(parse_extinf(""), (None, None));
+    }
+
+    #[test]
+    fn relative_paths_are_relative_to_the_playlist() {
+        let base = base();
+        let items = parse("a.mp4\nhttp://host/b.mp4\n", Some(&base));
+        assert_eq!(items[0].uri, file_uri(base.join("a.mp4")));
+        assert_eq!(items[1].uri, "http://host/b.mp4");
+    }
+
+    #[test]
+    fn empty_and_comment_only_input() {
+        assert!(parse("", None).is_empty());
+        assert!(parse("#EXTM3U\n#EXTINF:10,dangling\n", None).is_empty());
+    }
+
+    #[test]
+    fn round_trip() {
+        let base = base();
+        let items = vec![
+            PlaylistItem::new(file_uri(base.join("a.mp4")))
+                .title("A")
+                .duration(Duration::from_secs(61)),
+            PlaylistItem::new("http://host/b.mp4").title("B"),
+            PlaylistItem::new(file_uri(base.join("c d.mp4"))),
+        ];
+
+        let text = write(&items, Some(&base));
+        assert!(text.starts_with("#EXTM3U\n#EXTINF:61,A\na.mp4\n"));
+        assert!(text.contains("#EXTINF:-1,B\nhttp://host/b.mp4\n"));
+        assert!(text.ends_with("\nc d.mp4\n"));
+        assert_eq!(parse(&text, Some(&base)), items);
+    }
+}
@@ -0,0 +1,692 @@
+//! A queue of videos played one after the other
+//! attach it to a player with [`PlayerHandler::attach_playlist`](crate::player_handler::PlayerHandler::attach_playlist)
+//! to advance automatically at the end of each video.
+//! playlists can be read from and written to M3U, PLS and XSPF files
+
+mod format;
+mod m3u;
+mod pls;
+mod xspf;
+
+pub use format::{PlaylistError, PlaylistFormat};
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// an entry in a [`Playlist`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaylistItem {
+    /// the uri given to the player
+    pub uri: String,
+    /// the name shown instead of the uri
+    pub title: Option<String>,
+    /// the length of the video if the playlist file knows it
+    pub duration: Option<Duration>,
+}
+
+impl PlaylistItem {
+    /// create a new item playing the uri
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            title: None,
+            duration: None,
+        }
+    }
+
+    /// set the name shown instead of the uri
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// set the length of the video
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// the title, or the last part of the uri without a title
+    pub fn name(&self) -> &str {
+        self.title.as_deref().unwrap_or_else(|| {
+            self.uri
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or(&self.uri)
+        })
+    }
+}
+
+impl From<String> for PlaylistItem {
+    fn from(uri: String) -> Self {
+        Self::new(uri)
+    }
+}
+
+impl From<&str> for PlaylistItem {
+    fn from(uri: &str) -> Self {
+        Self::new(uri)
+    }
+}
+
+/// what happens at the end of a video
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Repeat {
+    /// stop after the last video
+    #[default]
+    Off,
+    /// play the current video again
+    One,
+    /// start over after the last video
+    All,
+}
+
+/// sent by the [`PlayerHandler`](crate::player_handler::PlayerHandler) when a playlist moves on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaylistEvent {
+    /// the player with the id started playing the item at the index
+    Changed {
+        /// the id of the player
+        id: String,
+        /// the index of the item in the playlist
+        index: usize,
+        /// the uri of the item
+        uri: String,
+    },
+    /// the player with the id played the last item and stopped
+    Finished {
+        /// the id of the player
+        id: String,
+    },
+}
+
+/// where the playlist is in its play order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cursor {
+    /// playing the item at this position
+    At(usize),
+    /// nothing is playing, the next item is at this position.
+    /// used before starting and after the current item was removed
+    Before(usize),
+}
+
+/// A list of videos with a current item, shuffle and repeat
+#[derive(Debug, Clone)]
+pub struct Playlist {
+    items: Vec<PlaylistItem>,
+    /// the indices of the items in the order they are played, shuffled when shuffle is on
+    order: Vec<usize>,
+    cursor: Cursor,
+    shuffle: bool,
+    repeat: Repeat,
+    seed: u64,
+}
+
+impl Default for Playlist {
+    fn default() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+
+        Self {
+            items: Vec::new(),
+            order: Vec::new(),
+            cursor: Cursor::Before(0),
+            shuffle: false,
+            repeat: Repeat::Off,
+            seed: seed | 1,
+        }
+    }
+}
+
+impl<I: Into<PlaylistItem>> FromIterator<I> for Playlist {
+    fn from_iter<T: IntoIterator<Item = I>>(items: T) -> Self {
+        let mut playlist = Self::default();
+        playlist.extend(items);
+        playlist
+    }
+}
+
+impl<I: Into<PlaylistItem>> Extend<I> for Playlist {
+    fn extend<T: IntoIterator<Item = I>>(&mut self, items: T) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+impl Playlist {
+    /// create an empty playlist
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the items in the order they were added
+    pub fn items(&self) -> &[PlaylistItem] {
+        &self.items
+    }
+
+    /// get the item at the index
+    pub fn get(&self, index: usize) -> Option<&PlaylistItem> {
+        self.items.get(index)
+    }
+
+    /// the number of items
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// returns true if there are no items
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// the index of the item being played
+    pub fn current(&self) -> Option<usize> {
+        match self.cursor {
+            Cursor::At(position) => self.order.get(position).copied(),
+            Cursor::Before(_) => None,
+        }
+    }
+
+    /// the item being played
+    pub fn current_item(&self) -> Option<&PlaylistItem> {
+        self.current().and_then(|index| self.items.get(index))
+    }
+
+    /// is shuffle on
+    pub fn shuffle(&self) -> bool {
+        self.shuffle
+    }
+
+    /// what happens at the end of a video
+    pub fn repeat(&self) -> Repeat {
+        self.repeat
+    }
+
+    /// set what happens at the end of a video
+    pub fn set_repeat(&mut self, repeat: Repeat) {
+        self.repeat = repeat;
+    }
+
+    /// turn shuffle on or off, the current item keeps playing.
+    /// the items after it are played in a random order while shuffle is on
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        if self.shuffle == shuffle {
+            return;
+        }
+        self.shuffle = shuffle;
+
+        let current = self.current();
+        if shuffle {
+            self.order = self.shuffled(current);
+            self.cursor = match current {
+                Some(_) => Cursor::At(0),
+                None => Cursor::Before(0),
+            };
+        } else {
+            let next = self.upcoming();
+            self.order = (0..self.items.len()).collect();
+            self.cursor = match (current, next) {
+                (Some(index), _) => Cursor::At(index),
+                (None, Some(index)) => Cursor::Before(index),
+                (None, None) => Cursor::Before(self.items.len()),
+            };
+        }
+    }
+
+    /// play the item at the index, returns [`None`] if there is no such item
+    pub fn play(&mut self, index: usize) -> Option<usize> {
+        let position = self.order.iter().position(|item| *item == index)?;
+        self.cursor = Cursor::At(position);
+        Some(index)
+    }
+
+    /// move to the next item, returns the index of the item to play.
+    /// returns [`None`] after the last item unless everything is repeated
+    pub fn play_next(&mut self) -> Option<usize> {
+        let position = match self.cursor {
+            Cursor::At(position) => position + 1,
+            Cursor::Before(position) => position,
+        };
+
+        if position < self.order.len() {
+            self.cursor = Cursor::At(position);
+        } else if self.repeat == Repeat::All && !self.is_empty() {
+            if self.shuffle {
+                // a new order every time around, without playing the same item twice in a row
+                let last = self.order.last().copied();
+                self.order = self.shuffled(None);
+                if self.order.len() > 1 && self.order.first().copied() == last {
+                    self.order.swap(0, 1);
+                }
+            }
+            self.cursor = Cursor::At(0);
+        } else {
+            return None;
+        }
+        self.current()
+    }
+
+    /// move to the previous item, returns the index of the item to play.
+    /// returns [`None`] before the first item unless everything is repeated
+    pub fn play_previous(&mut self) -> Option<usize> {
+        let position = match self.cursor {
+            Cursor::At(position) | Cursor::Before(position) => position,
+        };
+
+        if position > 0 {
+            self.cursor = Cursor::At(position.min(self.order.len()) - 1);
+        } else if self.repeat == Repeat::All && !self.is_empty() {
+            self.cursor = Cursor::At(self.order.len() - 1);
+        } else {
+            return None;
+        }
+        self.current()
+    }
+
+    /// move on at the end of a video, repeating the current item if asked to
+    pub fn advance(&mut self) -> Option<usize> {
+        match (self.repeat, self.current()) {
+            (Repeat::One, Some(index)) => Some(index),
+            _ => self.play_next(),
+        }
+    }
+
+    /// the index [`Playlist::advance`] will move to, without moving
+    pub fn peek_advance(&self) -> Option<usize> {
+        let position = match (self.repeat, self.cursor) {
+            (Repeat::One, Cursor::At(position)) => return self.order.get(position).copied(),
+            (_, Cursor::At(position)) => position + 1,
+            (_, Cursor::Before(position)) => position,
+        };
+
+        match self.order.get(position) {
+            Some(index) => Some(*index),
+            // starting over picks a new shuffled order
+            None if self.shuffle => self.clone().play_next(),
+            None if self.repeat == Repeat::All => self.order.first().copied(),
+            None => None,
+        }
+    }
+
+    /// add an item to the end
+    pub fn push(&mut self, item: impl Into<PlaylistItem>) {
+        self.insert(self.items.len(), item);
+    }
+
+    /// insert an item at the index, shifting the items after it.
+    /// with shuffle on it is played at a random point after the current item
+    pub fn insert(&mut self, index: usize, item: impl Into<PlaylistItem>) {
+        let index = index.min(self.items.len());
+        self.items.insert(index, item.into());
+        self.remap(|item| if item >= index { item + 1 } else { item });
+
+        let position = if self.shuffle {
+            let first = match self.cursor {
+                Cursor::At(position) => position + 1,
+                Cursor::Before(position) => position,
+            };
+            first + self.random(self.order.len() - first + 1)
+        } else {
+            index
+        };
+        self.order.insert(position, index);
+
+        self.cursor = match self.cursor {
+            Cursor::At(current) if position <= current => Cursor::At(current + 1),
+            Cursor::Before(next) if position < next => Cursor::Before(next + 1),
+            cursor => cursor,
+        };
+    }
+
+    /// remove the item at the index.
+    /// if it was playing the player keeps playing it, the next item is the one after it
+    pub fn remove(&mut self, index: usize) -> Option<PlaylistItem> {
+        let position = self.order.iter().position(|item| *item == index)?;
+        let item = self.items.remove(index);
+        let _ = self.order.remove(position);
+        self.remap(|item| if item > index { item - 1 } else { item });
+
+        self.cursor = match self.cursor {
+            Cursor::At(current) if position == current => Cursor::Before(current),
+            Cursor::At(current) if position < current => Cursor::At(current - 1),
+            Cursor::Before(next) if position < next => Cursor::Before(next - 1),
+            cursor => cursor,
+        };
+        Some(item)
+    }
+
+    /// move the item at `from` to `to`, the current item keeps playing
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        if from >= self.items.len() || to >= self.items.len() || from == to {
+            return;
+        }
+        let current = self.current();
+        let next = self.upcoming();
+
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        let moved = |item: usize| {
+            if item == from {
+                to
+            } else if from < to && (from + 1..=to).contains(&item) {
+                item - 1
+            } else if to < from && (to..from).contains(&item) {
+                item + 1
+            } else {
+                item
+            }
+        };
+        self.remap(moved);
+
+        // without shuffle the order follows the items
+        if !self.shuffle {
+            self.order = (0..self.items.len()).collect();
+            self.cursor = match (current.map(moved), next.map(moved)) {
+                (Some(index), _) => Cursor::At(index),
+                (None, Some(index)) => Cursor::Before(index),
+                (None, None) => Cursor::Before(self.items.len()),
+            };
+        }
+    }
+
+    /// remove all items
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.order.clear();
+        self.cursor = Cursor::Before(0);
+    }
+
+    /// the index of the item played by [`Playlist::play_next`] when nothing is playing
+    fn upcoming(&self) -> Option<usize> {
+        match self.cursor {
+            Cursor::Before(position) => self.order.get(position).copied(),
+            Cursor::At(_) => None,
+        }
+    }
+
+    /// change the indices in the play order, keeping the order itself
+    fn remap(&mut self, f: impl Fn(usize) -> usize) {
+        for item in &mut self.order {
+            *item = f(*item);
+        }
+    }
+
+    /// all indices in a random order, starting with `first` if given
+    fn shuffled(&mut self, first: Option<usize>) -> Vec<usize> {
+        let mut order: Vec<usize> = first
+            .into_iter()
+            .chain((0..self.items.len()).filter(|index| Some(*index) != first))
+            .collect();
+
+        // fisher yates, leaving `first` in place
+        let start = usize::from(first.is_some());
+        for i in (start + 1..order.len()).rev() {
+            let j = start + self.random(i - start + 1);
+            order.swap(i, j);
+        }
+        order
+    }
+
+    /// a random number below `bound`, using xorshift
+    fn random(&mut self, bound: usize) -> usize {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        (self.seed % bound.max(1) as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playlist(len: usize) -> Playlist {
+        (0..len)
+            .map(|index| format!("file:///{index}.mp4"))
+            .collect()
+    }
+
+    /// the uri of the current item
+    fn current_uri(playlist: &Playlist) -> Option<&str> {
+        playlist.current_item().map(|item| item.uri.as_str())
+    }
+
+    #[test]
+    fn plays_in_order_and_stops() {
+        let mut playlist = playlist(3);
+        assert_eq!(playlist.current(), None);
+        assert_eq!(playlist.play_next(), Some(0));
+        assert_eq!(playlist.play_next(), Some(1));
+        assert_eq!(playlist.play_next(), Some(2));
+        assert_eq!(playlist.play_next(), None);
+        assert_eq!(playlist.current(), Some(2));
+        assert_eq!(playlist.play_previous(), Some(1));
+    }
+
+    #[test]
+    fn previous_stops_at_the_first_item() {
+        let mut playlist = playlist(3);
+        assert_eq!(playlist.play_previous(), None);
+        assert_eq!(playlist.play(0), Some(0));
+        assert_eq!(playlist.play_previous(), None);
+        assert_eq!(playlist.current(), Some(0));
+    }
+
+    #[test]
+    fn repeat_all_wraps_around() {
+        let mut playlist = playlist(3);
+        playlist.set_repeat(Repeat::All);
+        assert_eq!(playlist.play(2), Some(2));
+        assert_eq!(playlist.play_next(), Some(0));
+        assert_eq!(playlist.play_previous(), Some(2));
+    }
+
+    #[test]
+    fn repeat_one_only_repeats_on_advance() {
+        let mut playlist = playlist(3);
+        playlist.set_repeat(Repeat::One);
+        assert_eq!(playlist.play(1), Some(1));
+        assert_eq!(playlist.advance(), Some(1));
+        assert_eq!(playlist.play_next(), Some(2));
+        // nothing playing yet, advance starts at the first item
+        let mut playlist = self::playlist(2);
+        playlist.set_repeat(Repeat::One);
+        assert_eq!(playlist.advance(), Some(0));
+    }
+
+    #[test]
+    fn empty_playlist() {
+        let mut playlist = Playlist::new();
+        playlist.set_repeat(Repeat::All);
+        assert_eq!(playlist.play_next(), None);
+        assert_eq!(playlist.play_previous(), None);
+        assert_eq!(playlist.peek_advance(), None);
+        assert_eq!(playlist.play(0), None);
+        assert_eq!(playlist.remove(0), None);
+    }
+
+    #[test]
+    fn removing_the_current_item_plays_the_one_after_it() {
+        let mut playlist = playlist(3);
+        let _ = playlist.play(1);
+        let removed = playlist.remove(1);
+        assert_eq!(removed.map(|item| item.uri), Some("file:///1.mp4".into()));
+        assert_eq!(playlist.current(), None);
+        assert_eq!(playlist.peek_advance(), Some(1));
+        assert_eq!(playlist.play_next(), Some(1));
+        assert_eq!(current_uri(&playlist), Some("file:///2.mp4"));
+    }
+
+    #[test]
+    fn removing_the_current_last_item_stops() {
+        let mut playlist = playlist(3);
+        let _ = playlist.play(2);
+        let _ = playlist.remove(2);
+        assert_eq!(playlist.play_next(), None);
+        assert_eq!(playlist.play_previous(), Some(1));
+    }
+
+    #[test]
+    fn removing_before_the_current_item_keeps_it() {
+        let mut playlist = playlist(4);
+        let _ = playlist.play(2);
+        let _ = playlist.remove(0);
+        assert_eq!(playlist.current(), Some(1));
+        assert_eq!(current_uri(&playlist), Some("file:///2.mp4"));
+        // and after it
+        let _ = playlist.remove(2);
+        assert_eq!(current_uri(&playlist), Some("file:///2.mp4"));
+        assert_eq!(playlist.play_next(), None);
+    }
+
+    #[test]
+    fn inserting_keeps_the_current_item() {
+        let mut playlist = playlist(3);
+        let _ = playlist.play(1);
+        playlist.insert(0, "file:///new.mp4");
+        assert_eq!(playlist.current(), Some(2));
+        assert_eq!(current_uri(&playlist), Some("file:///1.mp4"));
+
+        // inserted at the index of the current item, it moves the current item along
+        playlist.insert(2, "file:///before.mp4");
+        assert_eq!(playlist.current(), Some(3));
+        assert_eq!(current_uri(&playlist), Some("file:///1.mp4"));
+
+        // inserted right after the current item, it is played next
+        playlist.insert(4, "file:///next.mp4");
+        assert_eq!(playlist.peek_advance(), Some(4));
+        let _ = playlist.play_next();
+        assert_eq!(current_uri(&playlist), Some("file:///next.mp4"));
+
+        // out of range inserts go to the end
+        playlist.insert(100, "file:///end.mp4");
+        assert_eq!(
+            playlist.items().last().map(PlaylistItem::name),
+            Some("end.mp4")
+        );
+        assert_eq!(playlist.play_next(), Some(5));
+        assert_eq!(playlist.play_next(), Some(6));
+        assert_eq!(current_uri(&playlist), Some("file:///end.mp4"));
+    }
+
+    #[test]
+    fn moving_across_the_current_item() {
+        let mut playlist = playlist(4);
+        let _ = playlist.play(1);
+
+        // from before to after the current item
+        playlist.move_item(0, 3);
+        assert_eq!(playlist.current(), Some(0));
+        assert_eq!(current_uri(&playlist), Some("file:///1.mp4"));
+        assert_eq!(playlist.play_next(), Some(1));
+        assert_eq!(current_uri(&playlist), Some("file:///2.mp4"));
+
+        // from after to before the current item
+        playlist.move_item(3, 0);
+        assert_eq!(current_uri(&playlist), Some("file:///2.mp4"));
+        assert_eq!(playlist.current(), Some(2));
+
+        // the current item itself
+        playlist.move_item(2, 0);
+        assert_eq!(playlist.current(), Some(0));
+        assert_eq!(current_uri(&playlist), Some("file:///2.mp4"));
+
+        // out of range moves do nothing
+        let items = playlist.items().to_vec();
+        playlist.move_item(0, 4);
+        playlist.move_item(4, 0);
+        assert_eq!(playlist.items(), items);
+    }
+
+    #[test]
+    fn moving_keeps_the_next_item_after_a_removal() {
+        let mut playlist = playlist(4);
+        let _ = playlist.play(1);
+        let _ = playlist.remove(1);
+        // 2 is next, moving it to the front keeps it next
+        playlist.move_item(1, 0);
+        assert_eq!(playlist.peek_advance(), Some(0));
+        let _ = playlist.play_next();
+        assert_eq!(current_uri(&playlist), Some("file:///2.mp4"));
+    }
+
+    #[test]
+    fn shuffle_plays_every_item_once() {
+        let mut playlist = playlist(10);
+        let _ = playlist.play(3);
+        playlist.set_shuffle(true);
+        assert_eq!(playlist.current(), Some(3));
+
+        let mut played = vec![3];
+        while let Some(index) = playlist.play_next() {
+            played.push(index);
+        }
+        played.sort_unstable();
+        assert_eq!(played, (0..10).collect::<Vec<_>>());
+
+        playlist.set_shuffle(false);
+        let current = playlist.current();
+        assert!(current.is_some());
+        assert_eq!(
+            playlist.play_next(),
+            current.map(|index| index + 1).filter(|i| *i < 10)
+        );
+    }
+
+    #[test]
+    fn shuffle_repeat_all_does_not_play_an_item_twice_in_a_row() {
+        let mut playlist = playlist(5);
+        playlist.set_shuffle(true);
+        playlist.set_repeat(Repeat::All);
+        let mut last = playlist.play_next();
+        for _ in 0..50 {
+            let next = playlist.play_next();
+            assert!(next.is_some());
+            assert_ne!(next, last);
+            last = next;
+        }
+    }
+
+    #[test]
+    fn shuffled_inserts_are_played_after_the_current_item() {
+        let mut playlist = playlist(5);
+        playlist.set_shuffle(true);
+        let _ = playlist.play_next();
+        let current = playlist.current();
+        playlist.push("file:///new.mp4");
+        assert_eq!(
+            playlist.current().and_then(|i| playlist.get(i)),
+            current.and_then(|i| playlist.get(i))
+        );
+
+        let mut found = false;
+        while let Some(index) = playlist.play_next() {
+            found |= playlist.get(index).map(PlaylistItem::name) == Some("new.mp4");
+        }
+        assert!(found);
+    }
+
+    #[test]
+    fn peek_advance_matches_advance() {
+        for repeat in [Repeat::Off, Repeat::One, Repeat::All] {
+            for shuffle in [false, true] {
+                let mut playlist = playlist(4);
+                playlist.set_repeat(repeat);
+                playlist.set_shuffle(shuffle);
+                for _ in 0..10 {
+                    let peeked = playlist.peek_advance();
+                    assert_eq!(peeked, playlist.advance(), "{repeat:?} shuffle {shuffle}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn item_names() {
+        assert_eq!(PlaylistItem::new("file:///videos/a.mp4").name(), "a.mp4");
+        assert_eq!(PlaylistItem::new("http://host/dir/").name(), "dir");
+        assert_eq!(PlaylistItem::new("a.mp4").title("A").name(), "A");
+    }
+}
@@ -0,0 +1,191 @@
+//! PLS playlists
+//! an ini file with numbered `File`, `Title` and `Length` entries in a `[playlist]` section
+
+use std::{collections::BTreeMap, fmt::Write, path::Path, time::Duration};
+
+use super::{
+    format::{path_to_uri, uri_to_path},
+    PlaylistError, PlaylistItem,
+};
+
+/// read the entries of a pls playlist
+pub(super) fn parse(text: &str, base: Option<&Path>) -> Result<Vec<PlaylistItem>, PlaylistError> {
+    let mut section = None;
+    let mut entries: BTreeMap<u32, PlaylistItem> = BTreeMap::new();
+
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = Some(name.trim().to_ascii_lowercase());
+            continue;
+        }
+        if section.as_deref() != Some("playlist") {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(PlaylistError::Parse(format!(
+                "expected `key=value`, got `{}`",
+                line
+            )));
+        };
+        let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+
+        let number_at = key.find(|c: char| c.is_ascii_digit()).unwrap_or(key.len());
+        let (name, number) = key.split_at(number_at);
+        let Ok(number) = number.parse::<u32>() else {
+            // NumberOfEntries and Version are not needed
+            continue;
+        };
+
+        let entry = entries
+            .entry(number)
+            .or_insert_with(|| PlaylistItem::new(String::new()));
+        match name {
+            "file" => entry.uri = path_to_uri(value, base),
+            "title" if !value.is_empty() => entry.title = Some(value.to_string()),
+            "length" => entry.duration = value.parse::<u64>().ok().map(Duration::from_secs),
+            _ => {}
+        }
+    }
+
+    if section.is_none() {
+        return Err(PlaylistError::Parse(
+            "missing the `[playlist]` section".into(),
+        ));
+    }
+    Ok(entries
+        .into_values()
+        .filter(|item| !item.uri.is_empty())
+        .collect())
+}
+
+/// write the items as a pls playlist
+pub(super) fn write(items: &[PlaylistItem], base: Option<&Path>) -> String {
+    let mut text = String::from("[playlist]\n");
+    for (number, item) in (1..).zip(items) {
+        let _ = writeln!(text, "File{}={}", number, uri_to_path(&item.uri, base));
+        if let Some(title) = &item.title {
+            let _ = writeln!(text, "Title{}={}", number, title);
+        }
+        let seconds = item
+            .duration
+            .map_or(-1, |duration| duration.as_secs_f64().round() as i64);
+        let _ = writeln!(text, "Length{}={}", number, seconds);
+    }
+    let _ = writeln!(text, "NumberOfEntries={}", items.len());
+    text.push_str("Version=2\n");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use url::Url;
+
+    use super::*;
+
+    fn base() -> PathBuf {
+        std::env::temp_dir().join("playlists")
+    }
+
+    #[test]
+    fn parses_entries_in_number_order() {
+        let text = "[playlist]\n\
+                    ; a comment\n\
+                    File2=http://host/b.mp4\n\
+                    Title2=B\n\
+                    File1=http://host/a.mp4\n\
+                    Length1=90\n\
+                    Length2=-1\n\
+                    NumberOfEntries=2\n\
+                    Version=2\n";
+        assert_eq!(
+            parse(text, None).unwrap(),
+            vec![
+                PlaylistItem::new("http://host/a.mp4").duration(Duration::from_secs(90)),
+                PlaylistItem::new("http://host/b.mp4").title("B"),
+            ]
+        );
+    }
+
+    #[test]
+    fn number_of_entries_is_not_trusted() {
+        let text = "[playlist]\n\
+                    NumberOfEntries=5\n\
+                    File1=http://host/a.mp4\n\
+                    Title3=no file\n\
+                    File10=http://host/j.mp4\n";
+        let items = parse(text, None).unwrap();
+        let uris: Vec<&str> = items.iter().map(|item| item.uri.as_str()).collect();
+        assert_eq!(uris, ["http://host/a.mp4", "http://host/j.mp4"]);
+
+        let text = "[playlist]\nNumberOfEntries=0\nFile1=http://host/a.mp4\n";
+        assert_eq!(parse(text, None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn keys_and_sections_ignore_case() {
+        let text =
+            "[other]\nFile1=http://host/ignored.mp4\n[Playlist]\nfile1 = http://host/a.mp4\n";
+        let items = parse(text, None).unwrap();
+        assert_eq!(items, vec![PlaylistItem::new("http://host/a.mp4")]);
+    }
+
+    #[test]
+    fn relative_paths_are_relative_to_the_playlist() {
+        let base = base();
+        let items = parse("[playlist]\nFile1=a.mp4\n", Some(&base)).unwrap();
+        assert_eq!(
+            items[0].uri,
+            String::from(Url::from_file_path(base.join("a.mp4")).unwrap())
+        );
+    }
+
+    #[test]
+    fn empty_and_malformed_input() {
+        assert!(matches!(parse("", None), Err(PlaylistError::Parse(_))));
+        assert!(matches!(
+            parse("File1=http://host/a.mp4\n", None),
+            Err(PlaylistError::Parse(_))
+        ));
+        assert!(matches!(
+            parse("[playlist]\nFile1\n", None),
+            Err(PlaylistError::Parse(_))
+        ));
+        assert_eq!(parse("[playlist]\n", None).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let base = base();
+        let items = vec![
+            PlaylistItem::new(String::from(
+                Url::from_file_path(base.join("a.mp4")).unwrap(),
+            ))
+            .title("A = first")
+            .duration(Duration::from_secs(61)),
+            PlaylistItem::new("http://host/b.mp4"),
+        ];
+
+        let text = write(&items, Some(&base));
+        assert_eq!(
+            text,
+            "[playlist]\n\
+             File1=a.mp4\n\
+             Title1=A = first\n\
+             Length1=61\n\
+             File2=http://host/b.mp4\n\
+             Length2=-1\n\
+             NumberOfEntries=2\n\
+             Version=2\n"
+        );
+        assert_eq!(parse(&text, Some(&base)).unwrap(), items);
+    }
+}
@@ -0,0 +1,201 @@
+//! XSPF playlists
+//! xml with a `<track>` per entry holding its `<location>`, `<title>` and `<duration>` in milliseconds
+
+use std::{fmt::Write, path::Path, time::Duration};
+
+use quick_xml::{escape::escape, events::Event, Reader};
+
+use super::{
+    format::{relative_uri, resolve_uri},
+    PlaylistError, PlaylistItem,
+};
+
+/// the elements of a track that are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Location,
+    Title,
+    Duration,
+}
+
+/// read the tracks of a xspf playlist
+pub(super) fn parse(text: &str, base: Option<&Path>) -> Result<Vec<PlaylistItem>, PlaylistError> {
+    let parse_error = |err: quick_xml::Error| PlaylistError::Parse(err.to_string());
+
+    let mut reader = Reader::from_str(text);
+    let _ = reader.trim_text(true);
+
+    let mut items = Vec::new();
+    let mut track: Option<PlaylistItem> = None;
+    let mut field = None;
+
+    loop {
+        match reader.read_event().map_err(parse_error)? {
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"track" => track = Some(PlaylistItem::new(String::new())),
+                b"location" => field = Some(Field::Location),
+                b"title" => field = Some(Field::Title),
+                b"duration" => field = Some(Field::Duration),
+                _ => field = None,
+            },
+            Event::End(element) => {
+                field = None;
+                if element.local_name().as_ref() == b"track" {
+                    items.extend(track.take().filter(|track| !track.uri.is_empty()));
+                }
+            }
+            Event::Text(text) => {
+                let text = text.unescape().map_err(parse_error)?;
+                set_field(track.as_mut(), field, &text, base);
+            }
+            Event::CData(text) => {
+                let text = String::from_utf8_lossy(&text);
+                set_field(track.as_mut(), field, &text, base);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(items)
+}
+
+/// store the text of a field of the track, only the first location is used
+fn set_field(
+    track: Option<&mut PlaylistItem>,
+    field: Option<Field>,
+    text: &str,
+    base: Option<&Path>,
+) {
+    let (Some(track), Some(field)) = (track, field) else {
+        return;
+    };
+    let text = text.trim();
+
+    match field {
+        Field::Location if track.uri.is_empty() => track.uri = resolve_uri(text, base),
+        Field::Location => {}
+        Field::Title => track.title = Some(text.to_string()),
+        Field::Duration => track.duration = text.parse().ok().map(Duration::from_millis),
+    }
+}
+
+/// write the items as a xspf playlist
+pub(super) fn write(items: &[PlaylistItem], base: Option<&Path>) -> String {
+    let mut text = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n  <trackList>\n",
+    );
+    for item in items {
+        text.push_str("    <track>\n");
+        let _ = writeln!(
+            text,
+            "      <location>{}</location>",
+            escape(&relative_uri(&item.uri, base))
+        );
+        if let Some(title) = &item.title {
+            let _ = writeln!(text, "      <title>{}</title>", escape(title));
+        }
+        if let Some(duration) = item.duration {
+            let _ = writeln!(text, "      <duration>{}</duration>", duration.as_millis());
+        }
+        text.push_str("    </track>\n");
+    }
+    text.push_str("  </trackList>\n</playlist>\n");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use url::Url;
+
+    use super::*;
+
+    fn base() -> PathBuf {
+        std::env::temp_dir().join("playlists")
+    }
+
+    #[test]
+    fn parses_tracks_with_entities() {
+        let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <playlist version="1" xmlns="http://xspf.org/ns/0/">
+              <title>not a track</title>
+              <trackList>
+                <track>
+                  <location>http://host/a.mp4?x=1&amp;y=2</location>
+                  <location>http://host/mirror.mp4</location>
+                  <title>Tom &amp; Jerry &lt;3 &#233;</title>
+                  <duration>90500</duration>
+                </track>
+                <track>
+                  <title>no location</title>
+                </track>
+                <track>
+                  <location><![CDATA[http://host/b.mp4]]></location>
+                  <creator>ignored</creator>
+                </track>
+              </trackList>
+            </playlist>"#;
+
+        assert_eq!(
+            parse(text, None).unwrap(),
+            vec![
+                PlaylistItem::new("http://host/a.mp4?x=1&y=2")
+                    .title("Tom & Jerry <3 é")
+                    .duration(Duration::from_millis(90_500)),
+                PlaylistItem::new("http://host/b.mp4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn relative_locations_are_relative_to_the_playlist() {
+        let base = base();
+        let text = "<playlist><trackList><track><location>a%20b.mp4</location></track></trackList></playlist>";
+        let items = parse(text, Some(&base)).unwrap();
+        assert_eq!(
+            items[0].uri,
+            String::from(Url::from_file_path(base.join("a b.mp4")).unwrap())
+        );
+    }
+
+    #[test]
+    fn empty_and_malformed_input() {
+        assert_eq!(parse("", None).unwrap(), vec![]);
+        assert_eq!(
+            parse("<playlist><trackList/></playlist>", None).unwrap(),
+            vec![]
+        );
+        assert!(matches!(
+            parse("<playlist><trackList></playlist>", None),
+            Err(PlaylistError::Parse(_))
+        ));
+        assert!(matches!(
+            parse(
+                "<playlist><track><title>&bogus;</title></track></playlist>",
+                None
+            ),
+            Err(PlaylistError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let base = base();
+        let items = vec![
+            PlaylistItem::new(String::from(
+                Url::from_file_path(base.join("a b.mp4")).unwrap(),
+            ))
+            .title("Tom & Jerry <\"live\">")
+            .duration(Duration::from_millis(61_250)),
+            PlaylistItem::new("http://host/b.mp4?x=1&y=2"),
+        ];
+
+        let text = write(&items, Some(&base));
+        assert!(text.contains("<location>a%20b.mp4</location>"));
+        assert!(text.contains("<location>http://host/b.mp4?x=1&amp;y=2</location>"));
+        assert!(text.contains("<duration>61250</duration>"));
+        assert_eq!(parse(&text, Some(&base)).unwrap(), items);
+    }
+}
@@ -0,0 +1,308 @@
+//! A seek slider that keeps track of scrubbing itself.
+//! the position being dragged and the position waiting for the player to catch up
+//! are kept in the widget state, so the application only receives the final seek.
+
+use std::time::Duration;
+
+use iced::{
+    advanced::{
+        layout, renderer,
+        widget::{tree, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    event, mouse,
+    time::Instant,
+    touch,
+    widget::slider::{HandleShape, StyleSheet},
+    window, Border, Element, Event, Length, Point, Rectangle, Size,
+};
+
+/// How close the position of the player has to be to a seek before it counts as done.
+const SEEK_TOLERANCE: Duration = Duration::from_millis(500);
+
+/// How long the position of a seek is shown for when the player does not report it.
+const SEEK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A seek slider that emits a single message once the user lets go of it.
+#[allow(missing_debug_implementations)]
+pub struct SeekBar<'a, Message, Theme>
+where
+    Theme: StyleSheet,
+{
+    position: Duration,
+    duration: Duration,
+    on_seek: Box<dyn Fn(Duration) -> Message + 'a>,
+    width: Length,
+    height: f32,
+    style: Theme::Style,
+}
+
+impl<'a, Message, Theme> SeekBar<'a, Message, Theme>
+where
+    Theme: StyleSheet,
+{
+    /// The default height of a [`SeekBar`].
+    pub const DEFAULT_HEIGHT: f32 = 22.0;
+
+    /// Returns a new [`SeekBar`] showing the position of the player.
+    /// `on_seek` is called with the chosen position when the slider is released.
+    pub fn new(
+        position: Duration,
+        duration: Duration,
+        on_seek: impl Fn(Duration) -> Message + 'a,
+    ) -> Self {
+        Self {
+            position,
+            duration,
+            on_seek: Box::new(on_seek),
+            width: Length::Fill,
+            height: Self::DEFAULT_HEIGHT,
+            style: Default::default(),
+        }
+    }
+
+    /// Sets the width of the [`SeekBar`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the height of the [`SeekBar`].
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the style of the [`SeekBar`].
+    pub fn style(mut self, style: impl Into<Theme::Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// the position under the cursor
+    fn locate(&self, bounds: Rectangle, cursor_position: Point) -> Duration {
+        let percent = ((cursor_position.x - bounds.x) / bounds.width).clamp(0.0, 1.0);
+        self.duration.mul_f64(percent as f64)
+    }
+}
+
+/// the scrubbing state of a [`SeekBar`]
+#[derive(Debug, Default)]
+struct State {
+    /// the position being dragged to
+    dragging: Option<Duration>,
+    /// the position of the last seek and when it was made
+    pending: Option<(Duration, Instant)>,
+}
+
+impl State {
+    /// the position to show instead of the position of the player
+    fn displayed(&self) -> Option<Duration> {
+        self.dragging.or(self.pending.map(|(target, _)| target))
+    }
+
+    /// forget the last seek once the player has caught up with it or it timed out
+    fn confirm(&mut self, position: Duration, now: Instant) {
+        if let Some((target, seeked_at)) = self.pending {
+            if position.abs_diff(target) <= SEEK_TOLERANCE
+                || now.saturating_duration_since(seeked_at) >= SEEK_TIMEOUT
+            {
+                self.pending = None;
+            }
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer> for SeekBar<'a, Message, Theme>
+where
+    Renderer: iced::advanced::Renderer,
+    Theme: StyleSheet,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.state
+            .downcast_mut::<State>()
+            .confirm(self.position, Instant::now());
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::atomic(limits, self.width, self.height)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                if let Some(cursor) = cursor_position.position_over(bounds) {
+                    state.dragging = Some(self.locate(bounds, cursor));
+                    return event::Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+            | Event::Touch(touch::Event::FingerMoved { .. })
+                if state.dragging.is_some() =>
+            {
+                if let Some(cursor) = cursor_position.position() {
+                    state.dragging = Some(self.locate(bounds, cursor));
+                }
+                return event::Status::Captured;
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. })
+            | Event::Touch(touch::Event::FingerLost { .. }) => {
+                if let Some(target) = state.dragging.take() {
+                    let seeked_at = Instant::now();
+                    state.pending = Some((target, seeked_at));
+                    // the view may not be rebuilt while paused, redraw to drop a seek that timed out
+                    shell.request_redraw(window::RedrawRequest::At(seeked_at + SEEK_TIMEOUT));
+                    shell.publish((self.on_seek)(target));
+                    return event::Status::Captured;
+                }
+            }
+            Event::Window(_, window::Event::RedrawRequested(now)) => {
+                state.confirm(self.position, now);
+            }
+            _ => {}
+        }
+
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+
+        let appearance = if state.dragging.is_some() {
+            theme.dragging(&self.style)
+        } else if cursor_position.is_over(bounds) {
+            theme.hovered(&self.style)
+        } else {
+            theme.active(&self.style)
+        };
+
+        let (handle_width, handle_height, handle_border_radius) = match appearance.handle.shape {
+            HandleShape::Circle { radius } => (radius * 2.0, radius * 2.0, radius.into()),
+            HandleShape::Rectangle {
+                width,
+                border_radius,
+            } => (f32::from(width), bounds.height, border_radius),
+        };
+
+        let position = state.displayed().unwrap_or(self.position);
+        let offset = if self.duration.is_zero() {
+            0.0
+        } else {
+            (bounds.width - handle_width)
+                * (position.as_secs_f32() / self.duration.as_secs_f32()).clamp(0.0, 1.0)
+        };
+
+        let rail_y = bounds.y + bounds.height / 2.0;
+        let rail = |x: f32, width: f32| renderer::Quad {
+            bounds: Rectangle {
+                x,
+                y: rail_y - appearance.rail.width / 2.0,
+                width,
+                height: appearance.rail.width,
+            },
+            border: Border::with_radius(appearance.rail.border_radius),
+            ..renderer::Quad::default()
+        };
+
+        renderer.fill_quad(
+            rail(bounds.x, offset + handle_width / 2.0),
+            appearance.rail.colors.0,
+        );
+        renderer.fill_quad(
+            rail(
+                bounds.x + offset + handle_width / 2.0,
+                bounds.width - offset - handle_width / 2.0,
+            ),
+            appearance.rail.colors.1,
+        );
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    x: bounds.x + offset,
+                    y: rail_y - handle_height / 2.0,
+                    width: handle_width,
+                    height: handle_height,
+                },
+                border: Border {
+                    radius: handle_border_radius,
+                    width: appearance.handle.border_width,
+                    color: appearance.handle.border_color,
+                },
+                ..renderer::Quad::default()
+            },
+            appearance.handle.color,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if tree.state.downcast_ref::<State>().dragging.is_some() {
+            mouse::Interaction::Grabbing
+        } else if cursor_position.is_over(layout.bounds()) {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<SeekBar<'a, Message, Theme>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + iced::advanced::Renderer,
+    Theme: 'a + StyleSheet,
+    Message: 'a,
+{
+    fn from(seek_bar: SeekBar<'a, Message, Theme>) -> Self {
+        Element::new(seek_bar)
+    }
+}
@@ -0,0 +1,374 @@
+//! A widget that shows a preview popup while hovering a seek slider.
+//! the popup contains the storyboard thumbnail and timestamp at the cursor position.
+
+use std::time::Duration;
+
+use iced::{
+    advanced::{
+        image as image_renderer, layout, overlay, renderer,
+        text::{self as text_renderer, Text},
+        widget::{self, tree, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    alignment::{Horizontal, Vertical},
+    event, mouse,
+    widget::{image, text::LineHeight, text::Shaping},
+    window, Border, Element, Event, Length, Point, Rectangle, Shadow, Size, Vector,
+};
+
+use crate::{
+    helpers::helper_functions::{format_time, TimeFormat},
+    storyboard::Storyboard,
+    style::StyleSheet,
+};
+
+const TEXT_HEIGHT: f32 = 20.0;
+const POPUP_PADDING: f32 = 4.0;
+const POPUP_SPACING: f32 = 8.0;
+
+/// the position of the cursor while it is over the slider
+#[derive(Debug, Default)]
+struct State {
+    cursor: Option<Point>,
+}
+
+/// A widget that shows a thumbnail and timestamp above a seek slider when hovered.
+#[allow(missing_debug_implementations)]
+pub struct SeekPreview<'a, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    content: Element<'a, Message, Theme, Renderer>,
+    storyboard: Option<&'a Storyboard>,
+    duration: Duration,
+    thumbnail_width: f32,
+    time_format: TimeFormat,
+    framerate: Option<f64>,
+    style: Theme::Style,
+}
+
+impl<'a, Message, Theme, Renderer> SeekPreview<'a, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    /// Returns a new [`SeekPreview`] wrapping the seek slider
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        storyboard: Option<&'a Storyboard>,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            storyboard,
+            duration,
+            thumbnail_width: 160.0,
+            time_format: TimeFormat::default(),
+            framerate: None,
+            style: Default::default(),
+        }
+    }
+
+    /// the width the thumbnail is drawn at
+    pub fn thumbnail_width(mut self, width: f32) -> Self {
+        self.thumbnail_width = width;
+        self
+    }
+
+    /// Sets how the time under the cursor is written out.
+    /// the framerate is needed for the frame based formats.
+    pub fn time_format(mut self, time_format: TimeFormat, framerate: Option<f64>) -> Self {
+        self.time_format = time_format;
+        self.framerate = framerate;
+        self
+    }
+
+    /// Sets the style the popup is drawn with.
+    pub fn style(mut self, style: impl Into<Theme::Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for SeekPreview<'a, Message, Theme, Renderer>
+where
+    Renderer: text_renderer::Renderer + image_renderer::Renderer,
+    <Renderer as image_renderer::Renderer>::Handle: From<image::Handle>,
+    Theme: StyleSheet,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content]);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let cursor = cursor_position.position_over(layout.bounds());
+        if state.cursor != cursor {
+            state.cursor = cursor;
+            shell.request_redraw(window::RedrawRequest::NextFrame);
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let Tree {
+            state, children, ..
+        } = tree;
+        let content =
+            self.content
+                .as_widget_mut()
+                .overlay(&mut children[0], layout, renderer, translation);
+
+        // the popup is an overlay so it is not clipped or covered by the widgets around the slider
+        let bounds = layout.bounds();
+        let popup = state.downcast_ref::<State>().cursor.map(|cursor| {
+            let percent = ((cursor.x - bounds.x) / bounds.width).clamp(0.0, 1.0);
+            let time = self.duration.mul_f32(percent);
+
+            overlay::Element::new(Box::new(Popup::<Theme> {
+                slider: bounds + translation,
+                cursor_x: cursor.x + translation.x,
+                thumbnail: self
+                    .storyboard
+                    .and_then(|storyboard| storyboard.get(time))
+                    .cloned(),
+                thumbnail_width: self.thumbnail_width,
+                thumbnail_size: Size::ZERO,
+                label: format_time(time, self.time_format, self.framerate),
+                style: &self.style,
+            }))
+        });
+
+        match (content, popup) {
+            (Some(content), Some(popup)) => {
+                Some(overlay::Group::with_children(vec![content, popup]).overlay())
+            }
+            (content, popup) => content.or(popup),
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<SeekPreview<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + text_renderer::Renderer + image_renderer::Renderer,
+    <Renderer as image_renderer::Renderer>::Handle: From<image::Handle>,
+    Theme: 'a + StyleSheet,
+    Message: 'a,
+{
+    fn from(seek_preview: SeekPreview<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(seek_preview)
+    }
+}
+
+/// the popup above the slider with the thumbnail and timestamp under the cursor
+struct Popup<'a, Theme>
+where
+    Theme: StyleSheet,
+{
+    /// the bounds of the slider in window coordinates
+    slider: Rectangle,
+    cursor_x: f32,
+    thumbnail: Option<image::Handle>,
+    thumbnail_width: f32,
+    /// the size the thumbnail is drawn at, set in layout
+    thumbnail_size: Size,
+    label: String,
+    style: &'a Theme::Style,
+}
+
+impl<'a, Message, Theme, Renderer> overlay::Overlay<Message, Theme, Renderer> for Popup<'a, Theme>
+where
+    Renderer: text_renderer::Renderer + image_renderer::Renderer,
+    <Renderer as image_renderer::Renderer>::Handle: From<image::Handle>,
+    Theme: StyleSheet,
+{
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+        self.thumbnail_size = self.thumbnail.as_ref().map_or(Size::ZERO, |handle| {
+            let handle = <Renderer as image_renderer::Renderer>::Handle::from(handle.clone());
+            let dimensions = renderer.dimensions(&handle);
+            if dimensions.width == 0 {
+                Size::ZERO
+            } else {
+                Size::new(
+                    self.thumbnail_width,
+                    self.thumbnail_width * dimensions.height as f32 / dimensions.width as f32,
+                )
+            }
+        });
+
+        let size = Size::new(
+            self.thumbnail_size
+                .width
+                .max(self.thumbnail_width.min(80.0))
+                + POPUP_PADDING * 2.0,
+            self.thumbnail_size.height + TEXT_HEIGHT + POPUP_PADDING * 2.0,
+        );
+        let x = (self.cursor_x - size.width / 2.0)
+            .min(bounds.width - size.width)
+            .max(0.0);
+        let y = (self.slider.y - size.height - POPUP_SPACING).max(0.0);
+
+        layout::Node::new(size).move_to(Point::new(x, y))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+    ) {
+        let popup = layout.bounds();
+        let appearance = theme.appearance(self.style);
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: popup,
+                border: Border::with_radius(4.0),
+                shadow: Shadow::default(),
+            },
+            appearance.background,
+        );
+
+        if let Some(handle) = &self.thumbnail {
+            image_renderer::Renderer::draw(
+                renderer,
+                handle.clone().into(),
+                image::FilterMethod::Linear,
+                Rectangle::new(
+                    popup.position() + Vector::new(POPUP_PADDING, POPUP_PADDING),
+                    self.thumbnail_size,
+                ),
+            );
+        }
+
+        renderer.fill_text(
+            Text {
+                content: &self.label,
+                bounds: Size::new(popup.width, TEXT_HEIGHT),
+                size: renderer.default_size(),
+                line_height: LineHeight::default(),
+                font: renderer.default_font(),
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                shaping: Shaping::Basic,
+            },
+            Point::new(
+                popup.center_x(),
+                popup.y + POPUP_PADDING + self.thumbnail_size.height + TEXT_HEIGHT / 2.0,
+            ),
+            appearance.text_color,
+            popup,
+        );
+    }
+
+    // the popup only shows where the cursor is, it never takes the cursor from the slider
+    fn is_over(&self, _layout: Layout<'_>, _renderer: &Renderer, _cursor_position: Point) -> bool {
+        false
+    }
+}
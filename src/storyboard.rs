@@ -0,0 +1,43 @@
+//! A cache of preview thumbnails for a video
+//! filled in the background by a [`StoryboardBuilder`](playbin_core::StoryboardBuilder) subscription
+
+use std::{collections::BTreeMap, time::Duration};
+
+use iced::widget::image;
+
+/// preview thumbnails of a video ordered by the time they were taken at
+#[derive(Debug, Clone, Default)]
+pub struct Storyboard {
+    thumbnails: BTreeMap<Duration, image::Handle>,
+}
+
+impl Storyboard {
+    /// add a thumbnail taken at the given time
+    pub fn insert(&mut self, time: Duration, thumbnail: image::Handle) {
+        let _ = self.thumbnails.insert(time, thumbnail);
+    }
+
+    /// get the closest thumbnail at or before the given time
+    pub fn get(&self, time: Duration) -> Option<&image::Handle> {
+        self.thumbnails
+            .range(..=time)
+            .next_back()
+            .or_else(|| self.thumbnails.iter().next())
+            .map(|(_, thumbnail)| thumbnail)
+    }
+
+    /// the number of thumbnails generated so far
+    pub fn len(&self) -> usize {
+        self.thumbnails.len()
+    }
+
+    /// returns true if no thumbnails have been generated yet
+    pub fn is_empty(&self) -> bool {
+        self.thumbnails.is_empty()
+    }
+
+    /// remove all thumbnails
+    pub fn clear(&mut self) {
+        self.thumbnails.clear();
+    }
+}
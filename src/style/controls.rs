@@ -0,0 +1,466 @@
+//! The theme the video controls are drawn with.
+
+use iced::{
+    advanced::{
+        layout, overlay, renderer,
+        widget::{self, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    border::Radius,
+    event, mouse,
+    overlay::menu,
+    widget::{button, container, pick_list, scrollable, slider, svg, text},
+    Background, Border, Color, Element, Event, Length, Point, Rectangle, Size, Vector,
+};
+
+use super::{Appearance, StyleSheet};
+
+/// The theme the video controls are drawn with.
+/// holds the [`Appearance`] produced by the application theme, custom controls
+/// are drawn with it so they match the rest of the controls.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Controls(pub Appearance);
+
+impl Controls {
+    /// the background of menus, opaque so the video does not show through the options
+    fn menu_background(&self) -> Color {
+        Color {
+            a: 1.0,
+            ..self.0.background
+        }
+    }
+}
+
+impl StyleSheet for Controls {
+    type Style = ();
+
+    fn appearance(&self, _style: &Self::Style) -> Appearance {
+        self.0
+    }
+}
+
+/// The style of a button in the video controls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Button {
+    /// a plain button
+    #[default]
+    Default,
+    /// a toggle button that is switched on
+    Active,
+}
+
+impl button::StyleSheet for Controls {
+    type Style = Button;
+
+    fn active(&self, style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: match style {
+                Button::Default => None,
+                Button::Active => Some(Background::Color(self.0.button_pressed)),
+            },
+            text_color: self.0.icon_color,
+            border: Border::with_radius(4.0),
+            ..Default::default()
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: Some(Background::Color(self.0.button_hovered)),
+            ..self.active(style)
+        }
+    }
+
+    fn pressed(&self, style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: Some(Background::Color(self.0.button_pressed)),
+            ..self.active(style)
+        }
+    }
+}
+
+impl slider::StyleSheet for Controls {
+    type Style = ();
+
+    fn active(&self, _style: &Self::Style) -> slider::Appearance {
+        slider::Appearance {
+            rail: slider::Rail {
+                colors: (self.0.rail_filled, self.0.rail_empty),
+                width: self.0.rail_width,
+                border_radius: Radius::from(self.0.rail_width / 2.0),
+            },
+            handle: slider::Handle {
+                shape: slider::HandleShape::Circle {
+                    radius: self.0.handle_radius,
+                },
+                color: self.0.handle_color,
+                border_width: 0.0,
+                border_color: self.0.handle_color,
+            },
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> slider::Appearance {
+        self.active(style)
+    }
+
+    fn dragging(&self, style: &Self::Style) -> slider::Appearance {
+        let appearance = self.active(style);
+        slider::Appearance {
+            handle: slider::Handle {
+                shape: slider::HandleShape::Circle {
+                    radius: self.0.handle_radius * 1.25,
+                },
+                ..appearance.handle
+            },
+            ..appearance
+        }
+    }
+}
+
+impl svg::StyleSheet for Controls {
+    type Style = ();
+
+    fn appearance(&self, _style: &Self::Style) -> svg::Appearance {
+        svg::Appearance {
+            color: Some(self.0.icon_color),
+        }
+    }
+}
+
+impl text::StyleSheet for Controls {
+    type Style = ();
+
+    fn appearance(&self, _style: Self::Style) -> text::Appearance {
+        text::Appearance {
+            color: Some(self.0.text_color),
+        }
+    }
+}
+
+impl container::StyleSheet for Controls {
+    type Style = ();
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            text_color: Some(self.0.text_color),
+            ..Default::default()
+        }
+    }
+}
+
+impl pick_list::StyleSheet for Controls {
+    type Style = ();
+
+    fn active(&self, _style: &Self::Style) -> pick_list::Appearance {
+        pick_list::Appearance {
+            text_color: self.0.text_color,
+            placeholder_color: self.0.text_color,
+            handle_color: self.0.icon_color,
+            background: Background::Color(Color::TRANSPARENT),
+            border: Border::with_radius(4.0),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> pick_list::Appearance {
+        pick_list::Appearance {
+            background: Background::Color(self.0.button_hovered),
+            ..self.active(style)
+        }
+    }
+}
+
+impl menu::StyleSheet for Controls {
+    type Style = ();
+
+    fn appearance(&self, _style: &Self::Style) -> menu::Appearance {
+        menu::Appearance {
+            text_color: self.0.text_color,
+            background: Background::Color(self.menu_background()),
+            border: Border::with_radius(4.0),
+            selected_text_color: self.0.text_color,
+            selected_background: Background::Color(self.0.rail_filled),
+        }
+    }
+}
+
+impl scrollable::StyleSheet for Controls {
+    type Style = ();
+
+    fn active(&self, _style: &Self::Style) -> scrollable::Appearance {
+        scrollable::Appearance {
+            container: container::Appearance::default(),
+            scrollbar: scrollable::Scrollbar {
+                background: None,
+                border: Border::default(),
+                scroller: scrollable::Scroller {
+                    color: self.0.rail_empty,
+                    border: Border::with_radius(2.0),
+                },
+            },
+            gap: None,
+        }
+    }
+
+    fn hovered(
+        &self,
+        style: &Self::Style,
+        _is_mouse_over_scrollbar: bool,
+    ) -> scrollable::Appearance {
+        self.active(style)
+    }
+}
+
+/// A widget that draws its content with the [`Controls`] theme
+/// produced from the application theme.
+/// the alpha of the text colour it is drawn with fades every colour, the
+/// [`Overlay`](crate::overlay::Overlay) fades the controls in and out this way.
+#[allow(missing_debug_implementations)]
+pub(crate) struct Themed<'a, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    content: Element<'a, Message, Controls, Renderer>,
+    style: Theme::Style,
+}
+
+impl<'a, Message, Theme, Renderer> Themed<'a, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    /// Returns a new [`Themed`] drawing the content with the given style.
+    pub(crate) fn new(
+        content: impl Into<Element<'a, Message, Controls, Renderer>>,
+        style: Theme::Style,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            style,
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Themed<'a, Message, Theme, Renderer>
+where
+    Renderer: iced::advanced::Renderer,
+    Theme: StyleSheet,
+{
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content]);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let appearance = theme.appearance(&self.style).faded(style.text_color.a);
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            &Controls(appearance),
+            &renderer::Style {
+                text_color: appearance.text_color,
+            },
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let style = &self.style;
+
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+            .map(|content| overlay::Element::new(Box::new(ThemedOverlay { content, style })))
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+}
+
+/// the overlays of the content of a [`Themed`], e.g. the menu of a pick list
+struct ThemedOverlay<'b, Message, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    content: overlay::Element<'b, Message, Controls, Renderer>,
+    style: &'b Theme::Style,
+}
+
+impl<'b, Message, Theme, Renderer> overlay::Overlay<Message, Theme, Renderer>
+    for ThemedOverlay<'b, Message, Theme, Renderer>
+where
+    Renderer: iced::advanced::Renderer,
+    Theme: StyleSheet,
+{
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+        self.content.layout(renderer, bounds)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+    ) {
+        let appearance = theme.appearance(self.style);
+
+        self.content.draw(
+            renderer,
+            &Controls(appearance),
+            &renderer::Style {
+                text_color: appearance.text_color,
+            },
+            layout,
+            cursor_position,
+        );
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content.operate(layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.content
+            .on_event(event, layout, cursor_position, renderer, clipboard, shell)
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .mouse_interaction(layout, cursor_position, viewport, renderer)
+    }
+
+    fn is_over(&self, layout: Layout<'_>, renderer: &Renderer, cursor_position: Point) -> bool {
+        self.content.is_over(layout, renderer, cursor_position)
+    }
+
+    fn overlay<'c>(
+        &'c mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'c, Message, Theme, Renderer>> {
+        let style = self.style;
+
+        self.content
+            .overlay(layout, renderer)
+            .map(|content| overlay::Element::new(Box::new(ThemedOverlay { content, style })))
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Themed<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + iced::advanced::Renderer,
+    Theme: 'a + StyleSheet,
+    Theme::Style: 'a,
+    Message: 'a,
+{
+    fn from(themed: Themed<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(themed)
+    }
+}
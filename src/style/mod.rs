@@ -0,0 +1,204 @@
+//! Change the appearance of the video controls.
+//!
+//! the controls are drawn with their own theme so the application theme only has to
+//! implement [`StyleSheet`] instead of a style sheet for every widget in the controls.
+
+mod controls;
+
+pub(crate) use controls::Themed;
+pub use controls::{Button, Controls};
+
+use iced::{Color, Padding};
+
+/// The colours of the video controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Appearance {
+    /// The background of the controls bar and the seek preview.
+    pub background: Color,
+    /// The colour of the time text.
+    pub text_color: Color,
+    /// The colour of the icons on the buttons.
+    pub icon_color: Color,
+    /// The background of a hovered button.
+    pub button_hovered: Color,
+    /// The background of a pressed button.
+    pub button_pressed: Color,
+    /// The part of the slider rails before the handle.
+    pub rail_filled: Color,
+    /// The part of the slider rails after the handle.
+    pub rail_empty: Color,
+    /// The width of the slider rails.
+    pub rail_width: f32,
+    /// The colour of the slider handles.
+    pub handle_color: Color,
+    /// The radius of the slider handles.
+    pub handle_radius: f32,
+}
+
+impl Appearance {
+    /// light controls on a translucent black bar
+    pub fn dark() -> Self {
+        Self {
+            background: Color {
+                a: 0.80,
+                ..Color::BLACK
+            },
+            text_color: Color::WHITE,
+            icon_color: Color::WHITE,
+            button_hovered: Color::from_rgba(1.0, 1.0, 1.0, 0.10),
+            button_pressed: Color::from_rgba(1.0, 1.0, 1.0, 0.20),
+            rail_filled: Color::from_rgb8(0x5E, 0x7C, 0xE2),
+            rail_empty: Color::from_rgba(1.0, 1.0, 1.0, 0.30),
+            rail_width: 4.0,
+            handle_color: Color::WHITE,
+            handle_radius: 6.0,
+        }
+    }
+
+    /// dark controls on a translucent white bar
+    pub fn light() -> Self {
+        Self {
+            background: Color {
+                a: 0.85,
+                ..Color::WHITE
+            },
+            text_color: Color::BLACK,
+            icon_color: Color::BLACK,
+            button_hovered: Color::from_rgba(0.0, 0.0, 0.0, 0.08),
+            button_pressed: Color::from_rgba(0.0, 0.0, 0.0, 0.16),
+            rail_filled: Color::from_rgb8(0x5E, 0x7C, 0xE2),
+            rail_empty: Color::from_rgba(0.0, 0.0, 0.0, 0.20),
+            rail_width: 4.0,
+            handle_color: Color::from_rgb8(0x5E, 0x7C, 0xE2),
+            handle_radius: 6.0,
+        }
+    }
+}
+
+impl Appearance {
+    /// the appearance with every colour faded by the opacity
+    pub(crate) fn faded(self, opacity: f32) -> Self {
+        let fade = |color: Color| Color {
+            a: color.a * opacity,
+            ..color
+        };
+
+        Self {
+            background: fade(self.background),
+            text_color: fade(self.text_color),
+            icon_color: fade(self.icon_color),
+            button_hovered: fade(self.button_hovered),
+            button_pressed: fade(self.button_pressed),
+            rail_filled: fade(self.rail_filled),
+            rail_empty: fade(self.rail_empty),
+            handle_color: fade(self.handle_color),
+            ..self
+        }
+    }
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// The sizes of the video controls.
+/// these are needed to lay out the controls, before any theme is known.
+#[derive(Debug, Clone, Copy)]
+pub struct Metrics {
+    /// The width and height of the icons on the buttons.
+    pub icon_size: f32,
+    /// The space around the controls bar.
+    pub padding: Padding,
+    /// The space between the controls in the bar.
+    pub spacing: f32,
+    /// The height of the row of buttons under the seek slider.
+    pub bar_height: f32,
+    /// The width of the volume slider.
+    pub volume_width: f32,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            icon_size: 28.0,
+            padding: Padding::from([0, 8]),
+            spacing: 8.0,
+            bar_height: 60.0,
+            volume_width: 80.0,
+        }
+    }
+}
+
+/// A set of rules that dictate the style of the video controls.
+pub trait StyleSheet {
+    /// The supported style of the [`StyleSheet`].
+    type Style: Default;
+
+    /// Produces the [`Appearance`] of the video controls.
+    fn appearance(&self, style: &Self::Style) -> Appearance;
+
+    /// Produces the [`Metrics`] of the video controls.
+    fn metrics(style: &Self::Style) -> Metrics
+    where
+        Self: Sized,
+    {
+        let _ = style;
+        Metrics::default()
+    }
+}
+
+/// The style of the video controls for the built in [`iced::Theme`].
+#[derive(Debug, Clone, Copy, Default)]
+pub enum VideoControlsStyle {
+    /// dark or light controls following the palette of the theme
+    #[default]
+    Default,
+    /// always use [`Appearance::dark`]
+    Dark,
+    /// always use [`Appearance::light`]
+    Light,
+    /// a custom appearance with the default [`Metrics`]
+    Custom(fn(&iced::Theme) -> Appearance),
+    /// a custom appearance and metrics
+    CustomWithMetrics(fn(&iced::Theme) -> Appearance, Metrics),
+}
+
+impl StyleSheet for iced::Theme {
+    type Style = VideoControlsStyle;
+
+    fn appearance(&self, style: &Self::Style) -> Appearance {
+        match style {
+            VideoControlsStyle::Default => {
+                let palette = self.extended_palette();
+                let appearance = if palette.is_dark {
+                    Appearance::dark()
+                } else {
+                    Appearance::light()
+                };
+
+                Appearance {
+                    rail_filled: palette.primary.base.color,
+                    handle_color: if palette.is_dark {
+                        appearance.handle_color
+                    } else {
+                        palette.primary.base.color
+                    },
+                    ..appearance
+                }
+            }
+            VideoControlsStyle::Dark => Appearance::dark(),
+            VideoControlsStyle::Light => Appearance::light(),
+            VideoControlsStyle::Custom(appearance)
+            | VideoControlsStyle::CustomWithMetrics(appearance, _) => appearance(self),
+        }
+    }
+
+    fn metrics(style: &Self::Style) -> Metrics {
+        match style {
+            VideoControlsStyle::CustomWithMetrics(_, metrics) => *metrics,
+            _ => Metrics::default(),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! A widget that shows the elapsed or remaining time of a video.
+//! clicking it switches between the two, the choice is kept in the widget state.
+
+use iced::{
+    advanced::{
+        layout, renderer,
+        widget::{self, tree, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    event, mouse, touch, Element, Event, Length, Rectangle, Size,
+};
+
+/// A widget that switches between showing the elapsed and remaining time when clicked.
+#[allow(missing_debug_implementations)]
+pub struct TimeDisplay<'a, Message, Theme, Renderer> {
+    elapsed: Element<'a, Message, Theme, Renderer>,
+    remaining: Element<'a, Message, Theme, Renderer>,
+    show_remaining: bool,
+}
+
+impl<'a, Message, Theme, Renderer> TimeDisplay<'a, Message, Theme, Renderer> {
+    /// Returns a new [`TimeDisplay`] switching between the two elements.
+    pub fn new(
+        elapsed: impl Into<Element<'a, Message, Theme, Renderer>>,
+        remaining: impl Into<Element<'a, Message, Theme, Renderer>>,
+    ) -> Self {
+        Self {
+            elapsed: elapsed.into(),
+            remaining: remaining.into(),
+            show_remaining: false,
+        }
+    }
+
+    /// Show the remaining time until the [`TimeDisplay`] is first clicked.
+    pub fn show_remaining(mut self, show_remaining: bool) -> Self {
+        self.show_remaining = show_remaining;
+        self
+    }
+
+    fn shown(&self, state: &State) -> (usize, &Element<'a, Message, Theme, Renderer>) {
+        if state.remaining {
+            (1, &self.remaining)
+        } else {
+            (0, &self.elapsed)
+        }
+    }
+}
+
+/// which time is shown
+#[derive(Debug)]
+struct State {
+    remaining: bool,
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for TimeDisplay<'a, Message, Theme, Renderer>
+where
+    Renderer: iced::advanced::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State {
+            remaining: self.show_remaining,
+        })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.elapsed), Tree::new(&self.remaining)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.elapsed, &self.remaining]);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.elapsed.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let (index, shown) = self.shown(tree.state.downcast_ref::<State>());
+
+        shown
+            .as_widget()
+            .layout(&mut tree.children[index], renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. })
+                if cursor_position.is_over(layout.bounds()) =>
+            {
+                let state = tree.state.downcast_mut::<State>();
+                state.remaining = !state.remaining;
+                shell.invalidate_layout();
+
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let (index, shown) = self.shown(tree.state.downcast_ref::<State>());
+
+        shown.as_widget().draw(
+            &tree.children[index],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if cursor_position.is_over(layout.bounds()) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        let (index, shown) = self.shown(tree.state.downcast_ref::<State>());
+
+        shown
+            .as_widget()
+            .operate(&mut tree.children[index], layout, renderer, operation);
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<TimeDisplay<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + iced::advanced::Renderer,
+    Theme: 'a,
+    Message: 'a,
+{
+    fn from(time_display: TimeDisplay<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(time_display)
+    }
+}
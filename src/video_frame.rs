@@ -0,0 +1,187 @@
+//! A widget that displays the current frame of a video.
+//! keeps the aspect ratio of the stream and letterboxes the unused space.
+
+use iced::{
+    advanced::{image as image_renderer, layout, renderer, widget::Tree, Layout, Widget},
+    mouse,
+    widget::image,
+    Border, Color, ContentFit, Element, Length, Rectangle, Shadow, Size, Vector,
+};
+
+/// The aspect ratio used until the first frame arrives.
+const DEFAULT_ASPECT_RATIO: f32 = 16.0 / 9.0;
+
+/// The width used when the layout does not limit the width.
+const DEFAULT_WIDTH: f32 = 1280.0;
+
+/// A widget that displays the current frame of a video.
+#[allow(missing_debug_implementations)]
+pub struct VideoFrame {
+    frame: Option<image::Handle>,
+    width: Length,
+    height: Length,
+    content_fit: ContentFit,
+    letterbox: Color,
+}
+
+impl VideoFrame {
+    /// Returns a new [`VideoFrame`] showing the given frame.
+    pub fn new(frame: Option<image::Handle>) -> Self {
+        Self {
+            frame,
+            width: Length::Fill,
+            height: Length::Shrink,
+            content_fit: ContentFit::Contain,
+            letterbox: Color::BLACK,
+        }
+    }
+
+    /// Sets the width of the [`VideoFrame`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the height of the [`VideoFrame`].
+    /// [`Length::Shrink`] derives the height from the width and the aspect ratio of the stream.
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Sets how the frame is fit into the bounds of the [`VideoFrame`].
+    pub fn content_fit(mut self, content_fit: ContentFit) -> Self {
+        self.content_fit = content_fit;
+        self
+    }
+
+    /// Sets the colour drawn behind the frame where it does not cover the bounds.
+    pub fn letterbox(mut self, color: Color) -> Self {
+        self.letterbox = color;
+        self
+    }
+
+    fn frame_size<Renderer>(&self, renderer: &Renderer) -> Option<Size>
+    where
+        Renderer: image_renderer::Renderer,
+        <Renderer as image_renderer::Renderer>::Handle: From<image::Handle>,
+    {
+        let handle = self.frame.clone()?;
+        let Size { width, height } = renderer.dimensions(&handle.into());
+
+        (width > 0 && height > 0).then(|| Size::new(width as f32, height as f32))
+    }
+}
+
+/// the size of a video widget, [`Length::Shrink`] as height derives it from the aspect ratio
+pub(crate) fn layout_size(
+    width: Length,
+    height: Length,
+    limits: &layout::Limits,
+    frame_size: Option<Size>,
+) -> Size {
+    let aspect_ratio = frame_size.map_or(DEFAULT_ASPECT_RATIO, |size| size.width / size.height);
+
+    let max = limits.max();
+    let intrinsic_width = if max.width.is_finite() {
+        max.width
+    } else {
+        frame_size.map_or(DEFAULT_WIDTH, |size| size.width)
+    };
+
+    let mut size = limits.resolve(
+        width,
+        height,
+        Size::new(intrinsic_width, intrinsic_width / aspect_ratio),
+    );
+
+    if height == Length::Shrink {
+        size.height = (size.width / aspect_ratio).min(max.height);
+    }
+    size
+}
+
+/// where a frame of `frame_size` is drawn in the bounds, centered
+pub(crate) fn fit_bounds(content_fit: ContentFit, frame_size: Size, bounds: Rectangle) -> Rectangle {
+    let fitted = content_fit.fit(frame_size, bounds.size());
+    let offset = Vector::new(
+        (bounds.width - fitted.width) / 2.0,
+        (bounds.height - fitted.height) / 2.0,
+    );
+    Rectangle {
+        width: fitted.width,
+        height: fitted.height,
+        ..bounds
+    } + offset
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer> for VideoFrame
+where
+    Renderer: image_renderer::Renderer,
+    <Renderer as image_renderer::Renderer>::Handle: From<image::Handle>,
+{
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(layout_size(
+            self.width,
+            self.height,
+            limits,
+            self.frame_size(renderer),
+        ))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: Border::default(),
+                shadow: Shadow::default(),
+            },
+            self.letterbox,
+        );
+
+        let (Some(frame), Some(frame_size)) = (&self.frame, self.frame_size(renderer)) else {
+            return;
+        };
+
+        let drawing_bounds = fit_bounds(self.content_fit, frame_size, bounds);
+
+        renderer.with_layer(bounds, |renderer| {
+            image_renderer::Renderer::draw(
+                renderer,
+                frame.clone().into(),
+                image::FilterMethod::Linear,
+                drawing_bounds,
+            );
+        });
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<VideoFrame> for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + image_renderer::Renderer,
+    <Renderer as image_renderer::Renderer>::Handle: From<image::Handle>,
+{
+    fn from(video_frame: VideoFrame) -> Self {
+        Element::new(video_frame)
+    }
+}
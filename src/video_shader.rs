@@ -0,0 +1,577 @@
+//! A widget that displays the current frame of a video, converting yuv frames in a shader.
+//! rgba frames, and every frame on renderers without shaders like tiny-skia, are drawn as images.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use iced::{
+    advanced::{
+        image as image_renderer, layout, renderer,
+        widget::{tree, Tree},
+        Layout, Widget,
+    },
+    mouse,
+    widget::{
+        image,
+        shader::{self, wgpu, Shader, Storage},
+    },
+    Border, Color, ContentFit, Element, Length, Rectangle, Shadow, Size,
+};
+use playbin_core::{DecodedFrame, PixelFormat, YuvFrame};
+
+use crate::video_frame::{fit_bounds, layout_size};
+
+/// A widget that displays the current frame of a video, yuv frames are converted on the gpu.
+#[allow(missing_debug_implementations)]
+pub struct VideoShader {
+    key: u64,
+    frame: Option<DecodedFrame>,
+    width: Length,
+    height: Length,
+    content_fit: ContentFit,
+    letterbox: Color,
+}
+
+impl VideoShader {
+    /// Returns a new [`VideoShader`] showing the given frame.
+    /// the id tells apart the videos shown at the same time, e.g. the id of the player
+    pub fn new(id: impl Hash, frame: Option<DecodedFrame>) -> Self {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        id.hash(&mut hasher);
+
+        Self {
+            key: hasher.finish(),
+            frame,
+            width: Length::Fill,
+            height: Length::Shrink,
+            content_fit: ContentFit::Contain,
+            letterbox: Color::BLACK,
+        }
+    }
+
+    /// Sets the width of the [`VideoShader`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the height of the [`VideoShader`].
+    /// [`Length::Shrink`] derives the height from the width and the aspect ratio of the stream.
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Sets how the frame is fit into the bounds of the [`VideoShader`].
+    pub fn content_fit(mut self, content_fit: ContentFit) -> Self {
+        self.content_fit = content_fit;
+        self
+    }
+
+    /// Sets the colour drawn behind the frame where it does not cover the bounds.
+    pub fn letterbox(mut self, color: Color) -> Self {
+        self.letterbox = color;
+        self
+    }
+
+    fn frame_size(&self, renderer: &iced::Renderer) -> Option<Size> {
+        let (width, height) = match self.frame.as_ref()? {
+            DecodedFrame::Rgba(handle) => {
+                let size = image_renderer::Renderer::dimensions(renderer, handle);
+                (size.width, size.height)
+            }
+            DecodedFrame::Yuv(frame) => (frame.width(), frame.height()),
+        };
+
+        (width > 0 && height > 0).then(|| Size::new(width as f32, height as f32))
+    }
+}
+
+impl<Message, Theme> Widget<Message, Theme, iced::Renderer> for VideoShader {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<()>()
+    }
+
+    // the state of the shader program the yuv frames are drawn with
+    fn state(&self) -> tree::State {
+        tree::State::new(())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(layout_size(
+            self.width,
+            self.height,
+            limits,
+            self.frame_size(renderer),
+        ))
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        renderer::Renderer::fill_quad(
+            renderer,
+            renderer::Quad {
+                bounds,
+                border: Border::default(),
+                shadow: Shadow::default(),
+            },
+            self.letterbox,
+        );
+
+        let (Some(frame), Some(frame_size)) = (&self.frame, self.frame_size(renderer)) else {
+            return;
+        };
+
+        match frame {
+            DecodedFrame::Yuv(frame) if !matches!(renderer, iced::Renderer::TinySkia(_)) => {
+                let shader = Shader::new(YuvProgram {
+                    key: self.key,
+                    frame: frame.clone(),
+                    content_fit: self.content_fit,
+                });
+                Widget::<Message, Theme, iced::Renderer>::draw(
+                    &shader, tree, renderer, theme, style, layout, cursor, viewport,
+                );
+            }
+            // tiny-skia can not run shaders, the frame is converted on the cpu instead
+            frame => {
+                let drawing_bounds = fit_bounds(self.content_fit, frame_size, bounds);
+                renderer::Renderer::with_layer(renderer, bounds, |renderer| {
+                    image_renderer::Renderer::draw(
+                        renderer,
+                        frame.rgba().clone(),
+                        image::FilterMethod::Linear,
+                        drawing_bounds,
+                    );
+                });
+            }
+        }
+    }
+}
+
+impl<'a, Message, Theme> From<VideoShader> for Element<'a, Message, Theme, iced::Renderer> {
+    fn from(video_shader: VideoShader) -> Self {
+        Element::new(video_shader)
+    }
+}
+
+/// draws a yuv frame with [`YuvPipeline`]
+struct YuvProgram {
+    key: u64,
+    frame: YuvFrame,
+    content_fit: ContentFit,
+}
+
+impl<Message> shader::Program<Message> for YuvProgram {
+    type State = ();
+    type Primitive = YuvPrimitive;
+
+    fn draw(&self, _state: &(), _cursor: mouse::Cursor, bounds: Rectangle) -> YuvPrimitive {
+        let frame_size = Size::new(self.frame.width() as f32, self.frame.height() as f32);
+
+        YuvPrimitive {
+            key: self.key,
+            frame: self.frame.clone(),
+            drawing_bounds: fit_bounds(self.content_fit, frame_size, bounds),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct YuvPrimitive {
+    key: u64,
+    frame: YuvFrame,
+    drawing_bounds: Rectangle,
+}
+
+impl shader::Primitive for YuvPrimitive {
+    fn prepare(
+        &self,
+        format: wgpu::TextureFormat,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        _bounds: Rectangle,
+        target_size: Size<u32>,
+        scale_factor: f32,
+        storage: &mut Storage,
+    ) {
+        if !storage.has::<YuvPipeline>() {
+            storage.store(YuvPipeline::new(device, format));
+        }
+        let Some(pipeline) = storage.get_mut::<YuvPipeline>() else {
+            return;
+        };
+
+        // the corners of the frame in clip space
+        let to_clip = |x: f32, y: f32| {
+            [
+                x * scale_factor / target_size.width as f32 * 2.0 - 1.0,
+                1.0 - y * scale_factor / target_size.height as f32 * 2.0,
+            ]
+        };
+        let bounds = self.drawing_bounds;
+        let [left, top] = to_clip(bounds.x, bounds.y);
+        let [right, bottom] = to_clip(bounds.x + bounds.width, bounds.y + bounds.height);
+
+        pipeline.prepare(
+            device,
+            queue,
+            self.key,
+            &self.frame,
+            [left, top, right, bottom],
+            format.is_srgb(),
+        );
+    }
+
+    fn render(
+        &self,
+        storage: &Storage,
+        target: &wgpu::TextureView,
+        _target_size: Size<u32>,
+        viewport: Rectangle<u32>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        if let Some(pipeline) = storage.get::<YuvPipeline>() {
+            pipeline.render(self.key, target, viewport, encoder);
+        }
+    }
+}
+
+/// the size of the uniforms, the clip space corners and the flags
+const UNIFORMS_SIZE: u64 = 32;
+
+/// the textures of the planes of a video and the bind group to draw them
+struct VideoTextures {
+    /// the frame that was uploaded last
+    frame_id: u64,
+    format: PixelFormat,
+    size: (u32, u32),
+    textures: Vec<wgpu::Texture>,
+    uniforms: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+/// the render pipeline of [`VideoShader`], with the textures of every video it shows
+struct YuvPipeline {
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    videos: HashMap<u64, VideoTextures>,
+    /// the videos prepared since the current frame started, the others are no longer shown
+    used: HashSet<u64>,
+}
+
+impl YuvPipeline {
+    fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("iced_video yuv shader"),
+            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!(
+                "video_shader/yuv.wgsl"
+            ))),
+        });
+
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("iced_video yuv bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                texture_entry(1),
+                texture_entry(2),
+                texture_entry(3),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("iced_video yuv pipeline layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("iced_video yuv pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("iced_video yuv sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            layout,
+            sampler,
+            videos: HashMap::new(),
+            used: HashSet::new(),
+        }
+    }
+
+    /// upload the frame if it changed and move it to `rect`, in clip space
+    fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        key: u64,
+        frame: &YuvFrame,
+        rect: [f32; 4],
+        srgb: bool,
+    ) {
+        // every video is prepared once a frame, preparing one again starts the next frame.
+        // the textures of the videos that were not drawn in the last frame are dropped
+        if !self.used.insert(key) {
+            self.videos.retain(|key, _| self.used.contains(key));
+            self.used.clear();
+            let _ = self.used.insert(key);
+        }
+
+        let size = (frame.width(), frame.height());
+        let reusable = self
+            .videos
+            .get(&key)
+            .is_some_and(|video| video.format == frame.format() && video.size == size);
+        if !reusable {
+            let video = self.create_textures(device, frame);
+            let _ = self.videos.insert(key, video);
+        }
+        let Some(video) = self.videos.get_mut(&key) else {
+            return;
+        };
+
+        let mut uniforms = Vec::with_capacity(UNIFORMS_SIZE as usize);
+        for corner in rect {
+            uniforms.extend_from_slice(&corner.to_ne_bytes());
+        }
+        let flags = [
+            frame.format() == PixelFormat::Nv12,
+            frame.is_hd(),
+            srgb,
+            false,
+        ];
+        for flag in flags {
+            uniforms.extend_from_slice(&u32::from(flag).to_ne_bytes());
+        }
+        queue.write_buffer(&video.uniforms, 0, &uniforms);
+
+        if reusable && video.frame_id == frame.id() {
+            return;
+        }
+        video.frame_id = frame.id();
+
+        for (index, texture) in video.textures.iter().enumerate() {
+            let (Some((data, stride)), (width, height)) =
+                (frame.plane(index), frame.plane_size(index))
+            else {
+                continue;
+            };
+            let row = width as usize
+                * plane_format(frame.format(), index)
+                    .block_copy_size(None)
+                    .unwrap_or(1) as usize;
+            // a plane too short for its size would fail validation
+            if height == 0 || stride < row || data.len() < stride * (height as usize - 1) + row {
+                continue;
+            }
+
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                data,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(stride as u32),
+                    rows_per_image: None,
+                },
+                wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+    }
+
+    fn create_textures(&self, device: &wgpu::Device, frame: &YuvFrame) -> VideoTextures {
+        let textures: Vec<wgpu::Texture> = (0..frame.format().planes())
+            .map(|index| {
+                let (width, height) = frame.plane_size(index);
+                device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("iced_video yuv plane"),
+                    size: wgpu::Extent3d {
+                        width: width.max(1),
+                        height: height.max(1),
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: plane_format(frame.format(), index),
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                })
+            })
+            .collect();
+        let views: Vec<wgpu::TextureView> = textures
+            .iter()
+            .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()))
+            .collect();
+        // nv12 has its chroma in one plane, it is bound for both
+        let (u, v) = match frame.format() {
+            PixelFormat::Nv12 => (&views[1], &views[1]),
+            _ => (&views[1], &views[2]),
+        };
+
+        let uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("iced_video yuv uniforms"),
+            size: UNIFORMS_SIZE,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("iced_video yuv bind group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&views[0]),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(u),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(v),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        VideoTextures {
+            frame_id: frame.id(),
+            format: frame.format(),
+            size: (frame.width(), frame.height()),
+            textures,
+            uniforms,
+            bind_group,
+        }
+    }
+
+    fn render(
+        &self,
+        key: u64,
+        target: &wgpu::TextureView,
+        viewport: Rectangle<u32>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let Some(video) = self.videos.get(&key) else {
+            return;
+        };
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("iced_video yuv render pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_scissor_rect(viewport.x, viewport.y, viewport.width, viewport.height);
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &video.bind_group, &[]);
+        pass.draw(0..4, 0..1);
+    }
+}
+
+/// the texture format of a plane, the interleaved chroma of nv12 has two channels
+fn plane_format(format: PixelFormat, index: usize) -> wgpu::TextureFormat {
+    match (format, index) {
+        (PixelFormat::Nv12, 1) => wgpu::TextureFormat::Rg8Unorm,
+        _ => wgpu::TextureFormat::R8Unorm,
+    }
+}
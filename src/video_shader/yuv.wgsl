@@ -0,0 +1,64 @@
+// converts the planes of a yuv frame to rgb
+
+struct Uniforms {
+    // the corners of the frame in clip space, top left and bottom right
+    rect: vec4<f32>,
+    // nv12, BT.709 and srgb target flags
+    flags: vec4<u32>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var luma: texture_2d<f32>;
+@group(0) @binding(2) var chroma_u: texture_2d<f32>;
+@group(0) @binding(3) var chroma_v: texture_2d<f32>;
+@group(0) @binding(4) var frame_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let corner = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(mix(uniforms.rect.xy, uniforms.rect.zw, corner), 0.0, 1.0);
+    out.uv = corner;
+    return out;
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let u_sample = textureSample(chroma_u, frame_sampler, in.uv);
+    let v_sample = textureSample(chroma_v, frame_sampler, in.uv);
+
+    // limited range
+    let y = (textureSample(luma, frame_sampler, in.uv).r - 16.0 / 255.0) * 1.164383;
+    let chroma = select(
+        vec2<f32>(u_sample.r, v_sample.r),
+        u_sample.rg,
+        uniforms.flags.x == 1u,
+    ) - vec2<f32>(128.0 / 255.0);
+    let u = chroma.x;
+    let v = chroma.y;
+
+    var rgb: vec3<f32>;
+    if uniforms.flags.y == 1u {
+        rgb = vec3<f32>(y + 1.792741 * v, y - 0.213249 * u - 0.532909 * v, y + 2.112402 * u);
+    } else {
+        rgb = vec3<f32>(y + 1.596027 * v, y - 0.391762 * u - 0.812968 * v, y + 2.017232 * u);
+    }
+    rgb = clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0));
+
+    if uniforms.flags.z == 1u {
+        rgb = srgb_to_linear(rgb);
+    }
+    return vec4<f32>(rgb, 1.0);
+}
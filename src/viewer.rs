@@ -1,19 +1,44 @@
 //! Video viewer
 //! displays the video and the overlay
+use std::{fmt, hash::Hash, time::Duration};
+
 use iced::{
-    advanced::{layout, overlay, renderer, widget::Tree, Clipboard, Layout, Shell, Widget},
-    alignment::{Horizontal, Vertical},
-    event, mouse,
-    widget::{self, container, image, svg, text},
-    Alignment,  Color, Element, Event, Length, Point, Rectangle, Size,
+    widget::{self, container, horizontal_space, image, pick_list, svg, text},
+    Alignment, Color, ContentFit, Element, Length, Vector,
 };
-use playbin_core::AdvancedPlayer;
+use playbin_core::{AdvancedPlayer, DecodedFrame};
 
 use crate::{
-    helpers::{helper_functions::secs_to_hhmmss, svgs},
-    overlay::Overlay,
+    helpers::{
+        helper_functions::{format_time, TimeFormat},
+        svgs,
+    },
+    key_map::{KeyHandler, KeyMap},
+    overlay::{Gesture, Overlay},
+    seek_bar::SeekBar,
+    seek_preview::SeekPreview,
+    storyboard::Storyboard,
+    style::{self, StyleSheet},
+    time_display::TimeDisplay,
+    video_frame::VideoFrame,
+    video_shader::VideoShader,
 };
 
+/// The playback speeds offered by [`Control::Speed`].
+const SPEEDS: [Speed; 7] = [
+    Speed(0.25),
+    Speed(0.5),
+    Speed(0.75),
+    Speed(1.0),
+    Speed(1.25),
+    Speed(1.5),
+    Speed(2.0),
+];
+
+/// How far into a chapter the previous chapter button goes back to the start of the
+/// current chapter instead of the one before it.
+const PREVIOUS_CHAPTER_GRACE: Duration = Duration::from_secs(3);
+
 /// viewer event enum
 #[allow(missing_docs)]
 #[derive(Clone, Debug)]
@@ -22,17 +47,490 @@ pub enum ControlEvent {
     Pause,
     ToggleMute,
     Volume(f64),
-    Seek(f64),
-    Released,
+    /// the seek slider was released at the given position
+    SeekTo(Duration),
+    /// the video was clicked outside of the controls
+    Click,
+    /// the video was double clicked outside of the controls
+    DoubleClick,
+    /// the video was dragged by the given offset from where it was pressed
+    Drag(Vector),
+    /// a drag over the video was released
+    DragReleased,
+    /// a playback speed was chosen from [`Control::Speed`]
+    Speed(f64),
+    /// the [`Control::Loop`] button was pressed
+    ToggleLoop,
+    /// the [`Control::Fullscreen`] button was pressed
+    ToggleFullscreen,
+    /// the track with the given index was chosen from [`Control::Tracks`]
+    Track(usize),
+    /// skip to the chapter with the given index in [`Control::Chapters`]
+    Chapter(usize),
+}
+
+/// A control placed in the bar under the seek slider of a [`VideoView`].
+#[allow(missing_debug_implementations)]
+pub enum Control<'a, Message, Renderer = iced::Renderer> {
+    /// toggles between playing and paused
+    PlayPause,
+    /// the position and duration of the video, clicking it switches to the remaining time
+    Time,
+    /// mutes and unmutes the audio, the icon follows the volume
+    Mute,
+    /// a slider for the volume
+    Volume,
+    /// a menu to choose the playback speed
+    Speed,
+    /// toggles looping the video
+    Loop,
+    /// toggles fullscreen, emits [`ControlEvent::ToggleFullscreen`].
+    /// the application switches the window mode, see [`VideoView::fullscreen`]
+    Fullscreen,
+    /// a menu to choose one of the given tracks, e.g. audio or subtitle tracks
+    Tracks {
+        /// the names of the tracks
+        tracks: Vec<String>,
+        /// the index of the current track
+        selected: Option<usize>,
+    },
+    /// buttons to skip to the previous and next chapter, given the start of every chapter in order
+    Chapters(Vec<Duration>),
+    /// fills the remaining space, pushing the following controls to the end of the bar
+    Space,
+    /// a custom element, drawn with the [`Controls`](style::Controls) theme
+    Custom(Element<'a, Message, style::Controls, Renderer>),
+}
+
+impl<'a, Message, Renderer> Control<'a, Message, Renderer> {
+    /// The controls of a [`VideoView`] when none are set.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::PlayPause,
+            Self::Time,
+            Self::Space,
+            Self::Mute,
+            Self::Volume,
+            Self::Fullscreen,
+        ]
+    }
+
+    /// Returns a [`Control::Custom`] for the given element.
+    pub fn custom(element: impl Into<Element<'a, Message, style::Controls, Renderer>>) -> Self {
+        Self::Custom(element.into())
+    }
+}
+
+/// a playback speed shown in the speed menu
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Speed(f64);
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x", self.0)
+    }
+}
+
+/// a track shown in the track menu
+#[derive(Debug, Clone, PartialEq)]
+struct Track {
+    index: usize,
+    name: String,
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// draws the video with the size, fit and letterbox of the [`VideoView`]
+type DrawVideo<'a, Message, Theme, Renderer> = Box<
+    dyn FnOnce(Length, Length, ContentFit, Color) -> Element<'a, Message, Theme, Renderer> + 'a,
+>;
+
+/// A video player widget with an overlay of controls.
+/// the size of the video is derived from the stream, so it can be placed in any layout.
+/// the controls are styled with the [`StyleSheet`] of the theme.
+#[allow(missing_debug_implementations)]
+pub struct VideoView<'a, Message, P, F, Theme = iced::Theme, Renderer = iced::Renderer>
+where
+    Theme: StyleSheet,
+{
+    player: &'a P,
+    frame: Option<&'a image::Handle>,
+    video: Option<DrawVideo<'a, Message, Theme, Renderer>>,
+    storyboard: Option<&'a Storyboard>,
+    on_event: F,
+    width: Length,
+    height: Length,
+    content_fit: ContentFit,
+    letterbox: Color,
+    idle_timeout: Option<Duration>,
+    on_idle: Option<Box<dyn Fn(bool) -> Message + 'a>>,
+    fullscreen: bool,
+    time_format: TimeFormat,
+    show_remaining: bool,
+    controls: Vec<Control<'a, Message, Renderer>>,
+    key_map: Option<KeyMap>,
+    style: Theme::Style,
+}
+
+impl<'a, Message, P, F, Theme, Renderer> VideoView<'a, Message, P, F, Theme, Renderer>
+where
+    Theme: StyleSheet,
+{
+    /// Returns a new [`VideoView`] for the player and its latest frame.
+    /// the frame is drawn as an image, see [`VideoView::shader`] to draw yuv frames on the gpu.
+    pub fn new(player: &'a P, frame: Option<&'a image::Handle>, on_event: F) -> Self {
+        Self {
+            player,
+            frame,
+            video: None,
+            storyboard: None,
+            on_event,
+            width: Length::Fill,
+            height: Length::Shrink,
+            content_fit: ContentFit::Contain,
+            letterbox: Color::BLACK,
+            idle_timeout: Some(Duration::from_secs(3)),
+            on_idle: None,
+            fullscreen: false,
+            time_format: TimeFormat::default(),
+            show_remaining: false,
+            controls: Control::defaults(),
+            key_map: None,
+            style: Default::default(),
+        }
+    }
+
+    /// Sets the storyboard used to preview the video while hovering the seek slider.
+    pub fn storyboard(mut self, storyboard: Option<&'a Storyboard>) -> Self {
+        self.storyboard = storyboard;
+        self
+    }
+
+    /// Sets the width of the [`VideoView`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the height of the [`VideoView`].
+    /// [`Length::Shrink`] derives the height from the width and the aspect ratio of the stream.
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Sets how the video is fit into the bounds of the [`VideoView`].
+    pub fn content_fit(mut self, content_fit: ContentFit) -> Self {
+        self.content_fit = content_fit;
+        self
+    }
+
+    /// Sets the colour of the bars around the video when it does not fill the [`VideoView`].
+    pub fn letterbox(mut self, color: Color) -> Self {
+        self.letterbox = color;
+        self
+    }
+
+    /// Hide the controls after the mouse has been idle for the given time while playing.
+    /// `None` keeps the controls visible at all times.
+    pub fn idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Sets the message produced when the controls were hidden because the mouse is idle (`true`)
+    /// and when the mouse is used again (`false`), e.g. to hide the cursor through the window.
+    pub fn on_idle(mut self, on_idle: impl Fn(bool) -> Message + 'a) -> Self {
+        self.on_idle = Some(Box::new(on_idle));
+        self
+    }
+
+    /// Sets if the window is fullscreen, used for the icon of [`Control::Fullscreen`].
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
+
+    /// Sets how times are written out in the controls.
+    pub fn time_format(mut self, time_format: TimeFormat) -> Self {
+        self.time_format = time_format;
+        self
+    }
+
+    /// Show the remaining time instead of the elapsed time until [`Control::Time`] is clicked.
+    pub fn show_remaining(mut self, show_remaining: bool) -> Self {
+        self.show_remaining = show_remaining;
+        self
+    }
+
+    /// Sets the controls in the bar under the seek slider, in order.
+    /// defaults to [`Control::defaults`].
+    pub fn controls(
+        mut self,
+        controls: impl IntoIterator<Item = Control<'a, Message, Renderer>>,
+    ) -> Self {
+        self.controls = controls.into_iter().collect();
+        self
+    }
+
+    /// Adds a control to the end of the bar.
+    pub fn push_control(mut self, control: Control<'a, Message, Renderer>) -> Self {
+        self.controls.push(control);
+        self
+    }
+
+    /// Handle keyboard shortcuts with the given [`KeyMap`] while the [`VideoView`] is focused,
+    /// it is focused by clicking it. the shortcuts emit [`ControlEvent`]s like the controls.
+    pub fn key_map(mut self, key_map: KeyMap) -> Self {
+        self.key_map = Some(key_map);
+        self
+    }
+
+    /// Sets the style of the controls.
+    pub fn style(mut self, style: impl Into<Theme::Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+}
+
+impl<'a, Message, P, F, Theme> VideoView<'a, Message, P, F, Theme, iced::Renderer>
+where
+    Theme: StyleSheet,
+{
+    /// Draws the decoded frame with a [`VideoShader`] instead of the frame, yuv frames are
+    /// converted on the gpu. the id tells apart the videos shown at the same time, e.g. the id of the player
+    pub fn shader(mut self, id: impl Hash, frame: Option<&DecodedFrame>) -> Self {
+        let shader = VideoShader::new(id, frame.cloned());
+        self.video = Some(Box::new(move |width, height, content_fit, letterbox| {
+            shader
+                .width(width)
+                .height(height)
+                .content_fit(content_fit)
+                .letterbox(letterbox)
+                .into()
+        }));
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer, F, P> From<VideoView<'a, Message, P, F, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    P: AdvancedPlayer,
+    Message: std::clone::Clone + 'a,
+    Renderer: iced::advanced::text::Renderer
+        + iced::advanced::image::Renderer
+        + iced::advanced::svg::Renderer
+        + 'static,
+    Theme: StyleSheet + 'a,
+    Theme::Style: Clone + 'a,
+    F: Fn(ControlEvent) -> Message + Clone + 'a,
+    <Renderer as iced::advanced::image::Renderer>::Handle: From<image::Handle>,
+{
+    fn from(view: VideoView<'a, Message, P, F, Theme, Renderer>) -> Self {
+        let player = view.player;
+        let on_event = view.on_event;
+        let metrics = Theme::metrics(&view.style);
+
+        let duration = player.get_duration();
+        let position = player.get_position();
+        let volume = player.get_volume();
+        let framerate = player.get_framerate();
+        let format = |time| format_time(time, view.time_format, framerate);
+
+        let icon = |handle: svg::Handle| {
+            svg(handle)
+                .height(metrics.icon_size)
+                .width(metrics.icon_size)
+        };
+
+        let bar = view.controls.into_iter().map(
+            |control| -> Element<'a, Message, style::Controls, Renderer> {
+                match control {
+                    Control::PlayPause => {
+                        if player.is_playing() {
+                            widget::button(icon(svgs::pause_svg()))
+                                .on_press(on_event(ControlEvent::Pause))
+                                .into()
+                        } else {
+                            widget::button(icon(svgs::play_svg()))
+                                .on_press(on_event(ControlEvent::Play))
+                                .into()
+                        }
+                    }
+                    Control::Time => TimeDisplay::new(
+                        text(format!("{} / {}", format(position), format(duration))),
+                        text(format!(
+                            "-{} / {}",
+                            format(duration.saturating_sub(position)),
+                            format(duration)
+                        )),
+                    )
+                    .show_remaining(view.show_remaining)
+                    .into(),
+                    Control::Mute => {
+                        let volume_svg = if player.get_muted() {
+                            svgs::muted_svg()
+                        } else if volume > 0.66 {
+                            svgs::high_volume_svg()
+                        } else if volume > 0.33 {
+                            svgs::medium_volume_svg()
+                        } else if volume > 0.0 {
+                            svgs::low_volume_svg()
+                        } else {
+                            svgs::muted_svg()
+                        };
+
+                        widget::button(icon(volume_svg))
+                            .on_press(on_event(ControlEvent::ToggleMute))
+                            .into()
+                    }
+                    Control::Volume => {
+                        let on_volume = on_event.clone();
+                        widget::Slider::new(0.0..=1.0, volume, move |v| {
+                            on_volume(ControlEvent::Volume(v))
+                        })
+                        .step(0.05)
+                        .width(metrics.volume_width)
+                        .into()
+                    }
+                    Control::Speed => {
+                        let on_speed = on_event.clone();
+                        pick_list(
+                            &SPEEDS[..],
+                            Some(Speed(player.get_playback_rate())),
+                            move |speed| on_speed(ControlEvent::Speed(speed.0)),
+                        )
+                        .into()
+                    }
+                    Control::Loop => widget::button(icon(svgs::loop_svg()))
+                        .style(if player.get_looping() {
+                            style::Button::Active
+                        } else {
+                            style::Button::Default
+                        })
+                        .on_press(on_event(ControlEvent::ToggleLoop))
+                        .into(),
+                    Control::Fullscreen => widget::button(icon(if view.fullscreen {
+                        svgs::exit_fullscreen_svg()
+                    } else {
+                        svgs::fullscreen_svg()
+                    }))
+                    .on_press(on_event(ControlEvent::ToggleFullscreen))
+                    .into(),
+                    Control::Tracks { tracks, selected } => {
+                        let tracks: Vec<Track> = tracks
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, name)| Track { index, name })
+                            .collect();
+                        let selected = selected.and_then(|index| tracks.get(index).cloned());
+
+                        let on_track = on_event.clone();
+                        pick_list(tracks, selected, move |track| {
+                            on_track(ControlEvent::Track(track.index))
+                        })
+                        .placeholder("Tracks")
+                        .into()
+                    }
+                    Control::Chapters(chapters) => {
+                        let previous = chapters
+                            .iter()
+                            .rposition(|start| *start + PREVIOUS_CHAPTER_GRACE < position);
+                        let next = chapters.iter().position(|start| *start > position);
+
+                        widget::row![
+                            widget::button(icon(svgs::previous_chapter_svg())).on_press_maybe(
+                                previous.map(|index| on_event(ControlEvent::Chapter(index)))
+                            ),
+                            widget::button(icon(svgs::next_chapter_svg())).on_press_maybe(
+                                next.map(|index| on_event(ControlEvent::Chapter(index)))
+                            ),
+                        ]
+                        .into()
+                    }
+                    Control::Space => horizontal_space().into(),
+                    Control::Custom(element) => element,
+                }
+            },
+        );
+
+        let on_seek = on_event.clone();
+        let seek_bar = SeekBar::new(position, duration, move |position| {
+            on_seek(ControlEvent::SeekTo(position))
+        });
+        let seek_bar = SeekPreview::new(seek_bar, view.storyboard, duration)
+            .time_format(view.time_format, framerate);
+
+        let controls: Element<'a, Message, style::Controls, Renderer> = container(widget::column![
+            seek_bar,
+            widget::Row::with_children(bar)
+                .width(Length::Fill)
+                .height(metrics.bar_height)
+                .spacing(metrics.spacing)
+                .align_items(Alignment::Center)
+        ])
+        .padding(metrics.padding)
+        .width(Length::Fill)
+        .into();
+
+        let video = match view.video {
+            Some(video) => video(view.width, view.height, view.content_fit, view.letterbox),
+            None => VideoFrame::new(view.frame.cloned())
+                .width(view.width)
+                .height(view.height)
+                .content_fit(view.content_fit)
+                .letterbox(view.letterbox)
+                .into(),
+        };
+
+        let on_key = on_event.clone();
+        let fullscreen = view.fullscreen;
+
+        let overlay = Overlay::new(video, style::Themed::new(controls, view.style.clone()))
+            .style(view.style)
+            .idle_timeout(view.idle_timeout)
+            .keep_visible(!player.is_playing())
+            .on_gesture(move |gesture| {
+                on_event(match gesture {
+                    Gesture::Click => ControlEvent::Click,
+                    Gesture::DoubleClick => ControlEvent::DoubleClick,
+                    Gesture::Scroll(lines) => {
+                        ControlEvent::Volume((volume + lines as f64 * 0.05).clamp(0.0, 1.0))
+                    }
+                    Gesture::Drag(offset) => ControlEvent::Drag(offset),
+                    Gesture::DragReleased => ControlEvent::DragReleased,
+                })
+            });
+        let overlay = match view.on_idle {
+            Some(on_idle) => overlay.on_idle(on_idle),
+            None => overlay,
+        };
+
+        match view.key_map {
+            Some(key_map) => KeyHandler::new(overlay, key_map, move |action| {
+                action.control_event(player, fullscreen).map(&on_key)
+            })
+            .into(),
+            None => overlay.into(),
+        }
+    }
 }
 
 /// a viewer fuction to make an over easyliy
-pub fn video_view<'a, Message,Theme, Renderer, F, P>(
+/// hovering the seek slider shows a preview from the storyboard if one is given
+///
+/// shorthand for [`VideoView`] filling the available width
+pub fn video_view<'a, Message, Theme, Renderer, F, P>(
     player: &'a P,
     frame: Option<&'a image::Handle>,
+    storyboard: Option<&'a Storyboard>,
     on_event: &'a F,
-    seek_amount: &'a Option<u64>,
-) -> iced::Element<'a, Message,Theme, Renderer>
+) -> iced::Element<'a, Message, Theme, Renderer>
 where
     P: AdvancedPlayer,
     Message: std::clone::Clone + 'a,
@@ -40,95 +538,12 @@ where
         + iced::advanced::image::Renderer
         + iced::advanced::svg::Renderer
         + 'static,
-    Theme: widget::button::StyleSheet
-        + widget::text_input::StyleSheet
-        + widget::text::StyleSheet
-        + widget::slider::StyleSheet
-        + widget::container::StyleSheet
-        + widget::svg::StyleSheet + 'a,
+    Theme: StyleSheet + 'a,
+    Theme::Style: Clone + 'a,
     F: Fn(ControlEvent) -> Message + 'static + Clone,
     <Renderer as iced::advanced::image::Renderer>::Handle: From<image::Handle>,
 {
-    let i_width = 1280 as u16;
-    let i_height = (i_width as f32 * 9.0 / 16.0) as u16;
-    let width = Box::new(i_width);
-    let height = Box::new(i_height);
-    let player = Box::new(player);
-
-    let image = if let Some(handle) = frame {
-        iced::widget::image(handle.clone())
-            .height(i_height)
-            .width(i_width)
-    } else {
-        iced::widget::image(image::Handle::from_pixels(0, 0, vec![]))
-    };
-    let duration = player.get_duration().as_secs();
-    let position = if let Some(seek) = seek_amount {
-        seek.to_owned()
-    } else {
-        player.get_position().as_secs()
-    };
-
-    let play_pause = if player.is_playing() {
-        widget::Button::new(svg(svgs::pause_svg()).height(28).width(28))
-            // .style(theme::Button::Transparent)
-            .on_press(on_event(ControlEvent::Pause).clone())
-    } else {
-        widget::Button::new(svg(svgs::play_svg()).height(28).width(28))
-            .on_press(on_event(ControlEvent::Play).clone())
-    };
-
-    let duration_text = text(format!(
-        "{} / {}",
-        secs_to_hhmmss(position),
-        secs_to_hhmmss(duration)
-    ));
-
-    let volume = player.get_volume();
-    let volume_svg = if volume > 0.66 {
-        svgs::high_volume_svg()
-    } else if volume > 0.33 {
-        svgs::medium_volume_svg()
-    } else if volume > 0.0 {
-        svgs::low_volume_svg()
-    } else {
-        svgs::muted_svg()
-    };
-
-    let volume_button = if !player.get_muted() {
-        widget::Button::new(svg(volume_svg).height(28).width(28))
-            .on_press(on_event(ControlEvent::ToggleMute).clone())
-    } else {
-        widget::Button::new(svg(svgs::muted_svg()).height(28).width(28))
-            .on_press(on_event(ControlEvent::ToggleMute).clone())
-    };
-
-    let volume_slider = widget::Slider::new(0.0..=1.0, volume, |v| {
-        on_event(ControlEvent::Volume(v).clone())
-    })
-    .step(0.05)
-    .width(80);
-
-    let seek_slider = widget::Slider::new(
-        0.0..=duration.to_owned() as f64,
-        position.to_owned() as f64,
-        |v| on_event(ControlEvent::Seek(v).clone()),
-    )
-    .on_release(on_event(ControlEvent::Released).clone())
-    .step(1.0);
-
-    let overlay = container(widget::column![
-        seek_slider,
-        widget::row![play_pause, duration_text, volume_button, volume_slider]
-            .width(Length::Fill)
-            .height(60)
-            .align_items(Alignment::Center)
-    ])
-    .align_y(Vertical::Bottom)
-    .align_x(Horizontal::Left)
-    .width(*width)
-    .height(*height);
-
-    let content = Overlay::new(container(image).width(*width).height(*height), overlay);
-    container(content).into()
+    VideoView::new(player, frame, on_event)
+        .storyboard(storyboard)
+        .into()
 }